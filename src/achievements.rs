@@ -0,0 +1,441 @@
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
+
+use serenity::{
+    client::Context,
+    model::id::{GuildId, RoleId, UserId},
+};
+use tokio::time::interval;
+use tracing::error;
+
+use crate::{
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    strings::{ERR_ACHIEVEMENTS, ERR_CMD_ARGS_INVALID},
+};
+
+/// An achievement users of a guild can unlock.
+pub struct Achievement {
+    pub id: i32,
+    pub name: String,
+    pub criterion: Criterion,
+    pub goal: i64,
+    pub days: i32,
+    pub role: Option<RoleId>,
+}
+
+/// The criterion a user has to fulfill to unlock an achievement.
+#[derive(Clone, Copy)]
+pub enum Criterion {
+    Upvotes,
+    Score,
+    Voted,
+    Rank,
+    Pickups,
+}
+
+/// The progress of a user towards an achievement.
+pub struct Progress {
+    pub achievement: Achievement,
+    pub value: Option<i64>,
+    pub unlocked: bool,
+    pub holders: i64,
+}
+
+impl Display for Criterion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Criterion::Upvotes => "upvotes",
+            Criterion::Score => "score",
+            Criterion::Voted => "voted",
+            Criterion::Rank => "rank",
+            Criterion::Pickups => "pickups",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Criterion {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "upvotes" => Ok(Criterion::Upvotes),
+            "score" => Ok(Criterion::Score),
+            "voted" => Ok(Criterion::Voted),
+            "rank" => Ok(Criterion::Rank),
+            "pickups" => Ok(Criterion::Pickups),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+impl Achievement {
+    /// Describe what a user has to do to unlock the achievement.
+    pub fn description(&self) -> String {
+        match self.criterion {
+            Criterion::Upvotes => format!("Receive {} upvotes", self.goal),
+            Criterion::Score => format!("Reach a score of {}", self.goal),
+            Criterion::Voted => format!("Upvote {} different users", self.goal),
+            Criterion::Rank => {
+                if self.days > 0 {
+                    format!(
+                        "Stay in the top {} of the leaderboard for {} days",
+                        self.goal, self.days
+                    )
+                } else {
+                    format!("Reach the top {} of the leaderboard", self.goal)
+                }
+            }
+            Criterion::Pickups => format!("Pick up {} dropped scores", self.goal),
+        }
+    }
+
+    /// Check whether the given value fulfills the criterion of the achievement.
+    fn reached(&self, value: i64) -> bool {
+        match self.criterion {
+            Criterion::Rank => value <= self.goal,
+            _ => value >= self.goal,
+        }
+    }
+}
+
+/// Get all achievements of a guild including the progress of the given user.
+pub async fn get_progress(
+    database: &Database,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<Vec<Progress>, KowalskiError> {
+    // Get guild and user ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let user_db_id = database.get_user(guild_id, user_id).await?;
+
+    let rows = database
+        .client
+        .query(
+            "
+            SELECT a.id, name, criterion, goal, days, role,
+            EXISTS (
+                SELECT * FROM score_achievement_users au
+                WHERE au.achievement = a.id AND au.\"user\" = $2::BIGINT
+            ) unlocked,
+            (
+                SELECT COUNT(*) FROM score_achievement_users au
+                WHERE au.achievement = a.id
+            ) holders
+            FROM score_achievements a
+            LEFT JOIN score_achievement_rewards ar ON a.id = ar.achievement
+            WHERE a.guild = $1::BIGINT
+            ORDER BY criterion, goal, name
+            ",
+            &[&guild_db_id, &user_db_id],
+        )
+        .await?;
+
+    if rows.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let values = get_values(database, guild_db_id, user_db_id).await?;
+
+    let mut progress = Vec::new();
+
+    for row in rows {
+        let criterion = Criterion::from_str(row.get::<_, String>(2).as_str())?;
+
+        let achievement = Achievement {
+            id: row.get(0),
+            name: row.get(1),
+            criterion,
+            goal: row.get(3),
+            days: row.get(4),
+            role: row.get::<_, Option<i64>>(5).map(|role| RoleId(role as u64)),
+        };
+
+        progress.push(Progress {
+            achievement,
+            value: values.get(criterion),
+            unlocked: row.get(6),
+            holders: row.get(7),
+        });
+    }
+
+    Ok(progress)
+}
+
+/// Check whether the user has unlocked new achievements and hand out their rewards.
+///
+/// Note: Rank achievements which have to be held for some days are checked by `check_streaks`
+pub async fn check_achievements(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<(), KowalskiError> {
+    // Get guild and user ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let user_db_id = database.get_user(guild_id, user_id).await?;
+
+    let mut rewards = Vec::new();
+
+    for progress in get_progress(database, guild_id, user_id).await? {
+        let achievement = progress.achievement;
+
+        // Skip achievements which are already unlocked or not reached yet
+        if progress.unlocked
+            || !progress
+                .value
+                .is_some_and(|value| achievement.reached(value))
+        {
+            continue;
+        }
+
+        // Skip streaks
+        if matches!(achievement.criterion, Criterion::Rank) && achievement.days > 0 {
+            continue;
+        }
+
+        // Unlock the achievement
+        let modified = database
+            .client
+            .execute(
+                "
+                INSERT INTO score_achievement_users
+                VALUES ($1::INT, $2::BIGINT, $3::BIGINT, NOW())
+                ON CONFLICT
+                DO NOTHING
+                ",
+                &[&achievement.id, &guild_db_id, &user_db_id],
+            )
+            .await?;
+
+        if modified > 0 {
+            if let Some(role) = achievement.role {
+                rewards.push(role);
+            }
+        }
+    }
+
+    add_rewards(ctx, guild_id, user_id, rewards).await
+}
+
+pub fn check_streaks(ctx: Context, period: Duration) {
+    tokio::spawn(async move {
+        // Get database
+        let database = data!(ctx, Database);
+
+        // Create the interval at which we will check the streaks
+        let mut interval = interval(period);
+
+        loop {
+            // Wait for the next tick
+            interval.tick().await;
+
+            if let Err(why) = update_streaks(&ctx, &database).await {
+                error!("{}: {:?}", ERR_ACHIEVEMENTS, why);
+            }
+        }
+    });
+}
+
+// Start, end and complete the streaks of users staying in the top ranks of their guild
+async fn update_streaks(ctx: &Context, database: &Database) -> Result<(), KowalskiError> {
+    // Reset the streaks of users who have dropped out of the required ranks
+    database
+        .client
+        .execute(
+            "
+            WITH ranks AS (
+                SELECT guild, \"user\",
+                RANK() OVER (PARTITION BY guild ORDER BY score DESC, \"user\") rank
+                FROM scores
+                WHERE guild IN (SELECT guild FROM score_achievement_streaks)
+            )
+
+            DELETE FROM score_achievement_streaks s
+            USING score_achievements a
+            WHERE s.achievement = a.id
+            AND NOT EXISTS (
+                SELECT * FROM ranks r
+                WHERE r.guild = s.guild AND r.\"user\" = s.\"user\" AND r.rank <= a.goal
+            )
+            ",
+            &[],
+        )
+        .await?;
+
+    // Start the streaks of users who have entered the required ranks
+    database
+        .client
+        .execute(
+            "
+            WITH ranks AS (
+                SELECT guild, \"user\",
+                RANK() OVER (PARTITION BY guild ORDER BY score DESC, \"user\") rank
+                FROM scores
+                WHERE guild IN (
+                    SELECT guild FROM score_achievements
+                    WHERE criterion = $1::TEXT AND days > 0
+                )
+            )
+
+            INSERT INTO score_achievement_streaks
+            SELECT a.id, a.guild, r.\"user\", NOW()
+            FROM score_achievements a
+            INNER JOIN ranks r ON a.guild = r.guild AND r.rank <= a.goal
+            WHERE a.criterion = $1::TEXT AND a.days > 0
+            AND NOT EXISTS (
+                SELECT * FROM score_achievement_users au
+                WHERE au.achievement = a.id AND au.\"user\" = r.\"user\"
+            )
+            ON CONFLICT
+            DO NOTHING
+            ",
+            &[&Criterion::Rank.to_string()],
+        )
+        .await?;
+
+    // Unlock the achievements of completed streaks
+    let rows = database
+        .client
+        .query(
+            "
+            WITH unlocked AS (
+                INSERT INTO score_achievement_users
+                SELECT s.achievement, s.guild, s.\"user\", NOW()
+                FROM score_achievement_streaks s
+                INNER JOIN score_achievements a ON s.achievement = a.id
+                WHERE s.since + a.days * INTERVAL '1 day' <= NOW()
+                ON CONFLICT
+                DO NOTHING
+                RETURNING achievement, guild, \"user\"
+            )
+
+            SELECT u.guild, u.\"user\", ar.role
+            FROM unlocked u
+            LEFT JOIN score_achievement_rewards ar ON u.achievement = ar.achievement
+            ",
+            &[],
+        )
+        .await?;
+
+    // Hand out the reward roles per user
+    let mut rewards: HashMap<(GuildId, UserId), Vec<RoleId>> = HashMap::new();
+    for row in rows {
+        let guild_id = GuildId(row.get::<_, i64>(0) as u64);
+        let user_id = UserId(row.get::<_, i64>(1) as u64);
+        let roles = rewards.entry((guild_id, user_id)).or_default();
+
+        if let Some(role) = row.get::<_, Option<i64>>(2) {
+            roles.push(RoleId(role as u64));
+        }
+    }
+
+    for ((guild_id, user_id), roles) in rewards {
+        if let Err(why) = add_rewards(ctx, guild_id, user_id, roles).await {
+            error!("{}: {:?}", ERR_ACHIEVEMENTS, why);
+        }
+    }
+
+    Ok(())
+}
+
+// Hand out the reward roles of unlocked achievements
+async fn add_rewards(
+    ctx: &Context,
+    guild_id: GuildId,
+    user_id: UserId,
+    rewards: Vec<RoleId>,
+) -> Result<(), KowalskiError> {
+    if rewards.is_empty() {
+        return Ok(());
+    }
+
+    let mut member = guild_id.member(ctx, user_id).await?;
+
+    if !member.user.bot {
+        let add: Vec<_> = rewards
+            .into_iter()
+            .filter(|role| !member.roles.contains(role))
+            .collect();
+
+        if !add.is_empty() {
+            member.add_roles(&ctx.http, &add[..]).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The values of a user for all criteria.
+struct Values {
+    upvotes: i64,
+    score: i64,
+    voted: i64,
+    rank: Option<i64>,
+    pickups: i64,
+}
+
+impl Values {
+    fn get(&self, criterion: Criterion) -> Option<i64> {
+        match criterion {
+            Criterion::Upvotes => Some(self.upvotes),
+            Criterion::Score => Some(self.score),
+            Criterion::Voted => Some(self.voted),
+            Criterion::Rank => self.rank,
+            Criterion::Pickups => Some(self.pickups),
+        }
+    }
+}
+
+/// Get the current values of a user for all criteria.
+async fn get_values(
+    database: &Database,
+    guild_db_id: i64,
+    user_db_id: i64,
+) -> Result<Values, KowalskiError> {
+    let row = database
+        .client
+        .query_one(
+            "
+            WITH ranks AS (
+                SELECT \"user\", upvotes, score,
+                RANK() OVER (ORDER BY score DESC, \"user\") rank
+                FROM scores
+                WHERE guild = $1::BIGINT
+            )
+
+            SELECT
+            (SELECT upvotes FROM ranks WHERE \"user\" = $2::BIGINT),
+            (SELECT score FROM ranks WHERE \"user\" = $2::BIGINT),
+            (
+                SELECT COUNT(DISTINCT user_to) FROM score_reactions r
+                INNER JOIN score_emojis se ON r.guild = se.guild AND r.emoji = se.emoji
+                WHERE r.guild = $1::BIGINT AND user_from = $2::BIGINT AND upvote
+            ),
+            (SELECT rank FROM ranks WHERE \"user\" = $2::BIGINT),
+            (
+                SELECT pickups FROM score_pickups
+                WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
+            )
+            ",
+            &[&guild_db_id, &user_db_id],
+        )
+        .await?;
+
+    // Users without any rank have no value, all other criteria start at zero
+    Ok(Values {
+        upvotes: row.get::<_, Option<i64>>(0).unwrap_or_default(),
+        score: row.get::<_, Option<i64>>(1).unwrap_or_default(),
+        voted: row.get::<_, Option<i64>>(2).unwrap_or_default(),
+        rank: row.get(3),
+        pickups: row.get::<_, Option<i64>>(4).unwrap_or_default(),
+    })
+}
@@ -1,13 +1,38 @@
-use chrono::{Duration, Utc};
+use std::io::Cursor;
+
+use chrono::{DateTime, Duration, Utc};
 use ics::{
-    properties::{Description, DtEnd, DtStart, Summary},
+    components::Property,
+    escape_text,
+    parameters::CN,
+    properties::{
+        Description, DtEnd, DtStart, LastModified, Location, Organizer, Sequence, Status, Summary,
+        URL,
+    },
     Event, ICalendar,
 };
-use rocket::{get, routes, State};
-use serenity::{client::Context, model::id::GuildId};
+use rocket::{
+    get,
+    http::{ContentType, Status as HttpStatus},
+    request::{self, FromRequest},
+    response::{self, Responder},
+    routes, Request, Response, State,
+};
+use serenity::{
+    client::Context,
+    model::{
+        guild::ScheduledEventStatus,
+        id::{ChannelId, GuildId},
+    },
+};
+use sha2::{Digest, Sha256};
+use tokio_postgres::Row;
 use tracing::error;
 
-use crate::{data, database::client::Database, strings::ERR_CALENDAR};
+use crate::{data, database::client::Database, error::KowalskiError, strings::ERR_CALENDAR};
+
+const FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const HTTP_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
 
 pub fn host_calendar(ctx: Context) {
     tokio::spawn(async move {
@@ -22,18 +47,82 @@ pub fn host_calendar(ctx: Context) {
     });
 }
 
+/// Conditions of a request for a calendar the client already has a copy of.
+struct Conditions {
+    none_match: Option<String>,
+    modified_since: Option<DateTime<Utc>>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Conditions {
+    type Error = ();
+
+    async fn from_request(request: &'r Request<'_>) -> request::Outcome<Self, Self::Error> {
+        let headers = request.headers();
+
+        request::Outcome::Success(Conditions {
+            none_match: headers.get_one("If-None-Match").map(str::to_string),
+            modified_since: headers
+                .get_one("If-Modified-Since")
+                .and_then(|since| DateTime::parse_from_rfc2822(since).ok())
+                .map(|since| since.with_timezone(&Utc)),
+        })
+    }
+}
+
+impl Conditions {
+    // Check whether the copy of the client is still up to date
+    //
+    // Note: The modification time is only considered if the client didn't send an entity tag.
+    fn fresh(&self, etag: &str, modified: DateTime<Utc>) -> bool {
+        match (&self.none_match, self.modified_since) {
+            (Some(none_match), _) => none_match
+                .split(',')
+                .any(|tag| tag.trim() == "*" || tag.trim() == etag),
+            (None, Some(since)) => modified.timestamp() <= since.timestamp(),
+            (None, None) => false,
+        }
+    }
+}
+
+/// A calendar, or the note that the client's copy of it is still up to date.
+struct Calendar {
+    body: Option<String>,
+    etag: String,
+    modified: DateTime<Utc>,
+}
+
+impl<'r> Responder<'r, 'static> for Calendar {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let mut response = Response::build();
+        response.raw_header("ETag", self.etag).raw_header(
+            "Last-Modified",
+            self.modified.format(HTTP_FORMAT).to_string(),
+        );
+
+        match self.body {
+            Some(body) => response
+                .header(ContentType::Calendar)
+                .sized_body(body.len(), Cursor::new(body)),
+            None => response.status(HttpStatus::NotModified),
+        };
+
+        response.ok()
+    }
+}
+
 #[get("/<id>/events.ics")]
-async fn events(ctx: &State<Context>, id: String) -> Option<String> {
+async fn events(ctx: &State<Context>, id: String, conditions: Conditions) -> Option<Calendar> {
     // Get database
     let database = data!(ctx, Database);
 
-    // Get guild id
-    let guild_id = {
+    // Get guild id and name of the feed
+    let feed = {
         let row = database
             .client
             .query_opt(
                 "
-            SELECT guild FROM publishing
+            SELECT guild, name, modified FROM publishing
             Where id = $1::TEXT
         ",
                 &[&id],
@@ -41,41 +130,145 @@ async fn events(ctx: &State<Context>, id: String) -> Option<String> {
             .await
             .unwrap_or_default();
 
-        row.map(|row| GuildId(row.get::<_, i64>(0) as u64))
+        row.map(|row| {
+            (
+                GuildId(row.get::<_, i64>(0) as u64),
+                row.get::<_, String>(1),
+                row.get::<_, DateTime<Utc>>(2),
+            )
+        })
     };
 
-    const FORMAT: &str = "%Y%m%dT%H%M%SZ";
-
-    if let Some(guild_id) = guild_id {
-        // Get the scheduled events of the guild
-        if let Ok(events) = guild_id.scheduled_events(&ctx.http, false).await {
-            let mut calendar = ICalendar::new("2.0", "ics-rs");
-            for event in events {
-                let mut ics_event = Event::new(
-                    event.id.0.to_string(),
-                    Utc::now().format(FORMAT).to_string(),
-                );
-
-                ics_event.push(Summary::new(event.name));
-                if let Some(description) = event.description {
-                    ics_event.push(Description::new(description));
+    match feed {
+        Some((guild_id, name, feed_modified)) => {
+            match create_calendar(ctx, &database, guild_id, &name, &id).await {
+                Ok((body, events_modified)) => {
+                    // Deleted events only leave a mark on the modification time of the feed
+                    let modified = events_modified.map_or(feed_modified, |events_modified| {
+                        events_modified.max(feed_modified)
+                    });
+                    let etag = format!("\"{:x}\"", Sha256::digest(body.as_bytes()));
+
+                    let body = if conditions.fresh(&etag, modified) {
+                        None
+                    } else {
+                        Some(body)
+                    };
+
+                    Some(Calendar {
+                        body,
+                        etag,
+                        modified,
+                    })
+                }
+                Err(why) => {
+                    error!("{}: {:?}", ERR_CALENDAR, why);
+                    None
                 }
-                ics_event.push(DtStart::new(event.start_time.format(FORMAT).to_string()));
-                ics_event.push(DtEnd::new(
-                    event
-                        .end_time
-                        .map(|time| time.naive_utc())
-                        .unwrap_or(event.start_time.naive_utc() + Duration::hours(1))
-                        .format(FORMAT)
-                        .to_string(),
-                ));
-
-                calendar.add_event(ics_event);
             }
+        }
+        None => None,
+    }
+}
+
+// Create the calendar of a feed from the cached scheduled events matching its filters, along with
+// the latest modification time of those events
+async fn create_calendar(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    name: &str,
+    id: &str,
+) -> Result<(String, Option<DateTime<Utc>>), KowalskiError> {
+    let rows = database
+        .client
+        .query(
+            "
+            SELECT e.event, e.name, e.description, e.channel, e.location, e.creator,
+                e.creator_name, e.start, e.\"end\", e.status, e.sequence, e.modified
+            FROM scheduled_events e
+            INNER JOIN publishing p ON p.guild = e.guild
+            WHERE p.id = $1::TEXT
+                AND (p.channel IS NULL OR e.channel = p.channel)
+                AND (p.keyword IS NULL OR strpos(lower(e.name), lower(p.keyword)) > 0)
+                AND (p.creator IS NULL OR e.creator = p.creator)
+            ORDER BY e.start
+            ",
+            &[&id],
+        )
+        .await?;
 
-            return Some(calendar.to_string());
+    let mut calendar = ICalendar::new("2.0", "ics-rs");
+    if let Some(guild_name) = guild_id.name(ctx) {
+        calendar.push(Property::new(
+            "X-WR-CALNAME",
+            escape_text(format!("{} ({})", guild_name, name)),
+        ));
+    }
+    calendar.push(Property::new("X-WR-TIMEZONE", "UTC"));
+
+    for row in &rows {
+        calendar.add_event(create_event(ctx, guild_id, row).await);
+    }
+
+    let modified = rows.iter().map(|row| row.get::<_, DateTime<Utc>>(11)).max();
+
+    Ok((calendar.to_string(), modified))
+}
+
+async fn create_event<'a>(ctx: &Context, guild_id: GuildId, row: &Row) -> Event<'a> {
+    let event_id = row.get::<_, i64>(0) as u64;
+    let name: String = row.get(1);
+    let description: Option<String> = row.get(2);
+    let channel_id = row
+        .get::<_, Option<i64>>(3)
+        .map(|channel| ChannelId(channel as u64));
+    let location = match row.get::<_, Option<String>>(4) {
+        Some(location) => Some(location),
+        None => match channel_id {
+            Some(channel_id) => channel_id.name(ctx).await,
+            None => None,
+        },
+    };
+    let creator = row.get::<_, Option<i64>>(5).map(|creator| creator as u64);
+    let creator_name: Option<String> = row.get(6);
+    let start: DateTime<Utc> = row.get(7);
+    let end = row
+        .get::<_, Option<DateTime<Utc>>>(8)
+        .unwrap_or(start + Duration::hours(1));
+    let status = if row.get::<_, i32>(9) == ScheduledEventStatus::Canceled as i32 {
+        "CANCELLED"
+    } else {
+        "CONFIRMED"
+    };
+    let sequence: i32 = row.get(10);
+    let modified: DateTime<Utc> = row.get(11);
+
+    let mut ics_event = Event::new(event_id.to_string(), modified.format(FORMAT).to_string());
+
+    ics_event.push(Summary::new(escape_text(name)));
+    if let Some(description) = description {
+        ics_event.push(Description::new(escape_text(description)));
+    }
+    ics_event.push(DtStart::new(start.format(FORMAT).to_string()));
+    ics_event.push(DtEnd::new(end.format(FORMAT).to_string()));
+    if let Some(location) = location {
+        ics_event.push(Location::new(escape_text(location)));
+    }
+    ics_event.push(URL::new(format!(
+        "https://discord.com/events/{}/{}",
+        guild_id.0, event_id
+    )));
+    ics_event.push(Status::new(status));
+    if let Some(creator) = creator {
+        let mut organizer = Organizer::new(format!("https://discord.com/users/{}", creator));
+        if let Some(creator_name) = creator_name {
+            organizer.add(CN::new(creator_name));
         }
+        ics_event.push(organizer);
     }
+    ics_event.push(Sequence::new(sequence.to_string()));
+    ics_event.push(LastModified::new(modified.format(FORMAT).to_string()));
 
-    None
+    ics_event
 }
@@ -7,7 +7,8 @@ use tokio::sync::RwLock;
 use crate::model::Model;
 use crate::{
     config::Config, cooldowns::Cooldowns, credits::Credits, database::client::Database,
-    events::handler::Handler, history::History, strings::ERR_ENV_NOT_SET,
+    event_reminders::EventReminderScheduler, events::handler::Handler, history::History,
+    reminders::ReminderScheduler, strings::ERR_ENV_NOT_SET,
 };
 
 /// The bot client.
@@ -35,7 +36,9 @@ impl Client {
             | GatewayIntents::GUILD_MEMBERS
             | GatewayIntents::GUILD_EMOJIS_AND_STICKERS
             | GatewayIntents::GUILD_MESSAGES
-            | GatewayIntents::GUILD_MESSAGE_REACTIONS;
+            | GatewayIntents::GUILD_MESSAGE_REACTIONS
+            | GatewayIntents::GUILD_VOICE_STATES
+            | GatewayIntents::GUILD_SCHEDULED_EVENTS;
 
         #[cfg(feature = "nlp-model")]
         let intents = GatewayIntents::GUILDS
@@ -43,6 +46,8 @@ impl Client {
             | GatewayIntents::GUILD_EMOJIS_AND_STICKERS
             | GatewayIntents::GUILD_MESSAGES
             | GatewayIntents::GUILD_MESSAGE_REACTIONS
+            | GatewayIntents::GUILD_VOICE_STATES
+            | GatewayIntents::GUILD_SCHEDULED_EVENTS
             | GatewayIntents::MESSAGE_CONTENT;
 
         // Build the client
@@ -64,6 +69,10 @@ impl Client {
             data.insert::<Credits>(Arc::new(RwLock::new(Credits::new())));
             // Add query history to data
             data.insert::<History>(Arc::new(RwLock::new(History::new())));
+            // Add reminder scheduler to data
+            data.insert::<ReminderScheduler>(Arc::new(ReminderScheduler::default()));
+            // Add event reminder scheduler to data
+            data.insert::<EventReminderScheduler>(Arc::new(EventReminderScheduler::default()));
             #[cfg(feature = "nlp-model")]
             // Add nlp model to data
             data.insert::<Model>(Arc::new(Model::new().await?));
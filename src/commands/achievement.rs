@@ -0,0 +1,228 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue::Role,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    achievements::{Achievement, Criterion},
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    strings::ERR_CMD_ARGS_INVALID,
+    utils::{parse_arg, parse_arg_resolved, send_response},
+};
+
+enum Action {
+    Add,
+    Remove,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Action::Add => "Add",
+            Action::Remove => "Remove",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add" => Ok(Action::Add),
+            "remove" => Ok(Action::Remove),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let action = Action::from_str(parse_arg(options, 0)?)?;
+    let name: String = parse_arg(options, 1)?;
+    let mut criterion = None;
+    let mut goal = None;
+    let mut days = 0;
+    let mut role = None;
+    for i in 2..options.len() {
+        match options.get(i).unwrap().name.as_str() {
+            "criterion" => criterion = Some(Criterion::from_str(parse_arg(options, i)?)?),
+            "goal" => goal = Some(parse_arg(options, i)?),
+            "days" => days = parse_arg(options, i)?,
+            "role" => {
+                role = match parse_arg_resolved(options, i)? {
+                    Role(role) => Some(role),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    let title = format!("{} achievement {}", action, name);
+
+    match action {
+        Action::Add => {
+            let (criterion, goal) = match (criterion, goal) {
+                (Some(criterion), Some(goal)) => (criterion, goal),
+                _ => {
+                    return send_response(
+                        ctx,
+                        command,
+                        command_config,
+                        &title,
+                        "Please provide the criterion and the goal of the achievement.",
+                    )
+                    .await;
+                }
+            };
+
+            // Insert or update the achievement
+            let id: i32 = database
+                .client
+                .query_one(
+                    "
+            INSERT INTO score_achievements (guild, name, criterion, goal, days)
+            VALUES ($1::BIGINT, $2::TEXT, $3::TEXT, $4::BIGINT, $5::INT)
+            ON CONFLICT (guild, name)
+            DO UPDATE SET criterion = $3::TEXT, goal = $4::BIGINT, days = $5::INT
+            RETURNING id
+            ",
+                    &[&guild_db_id, &name, &criterion.to_string(), &goal, &days],
+                )
+                .await?
+                .get(0);
+
+            // Update the reward role
+            match role {
+                Some(role) => {
+                    let role_db_id = database.get_role(guild_id, role.id).await?;
+
+                    database
+                        .client
+                        .execute(
+                            "
+                    INSERT INTO score_achievement_rewards
+                    VALUES ($1::INT, $2::BIGINT, $3::BIGINT)
+                    ON CONFLICT (achievement)
+                    DO UPDATE SET role = $3::BIGINT
+                    ",
+                            &[&id, &guild_db_id, &role_db_id],
+                        )
+                        .await?;
+                }
+                None => {
+                    database
+                        .client
+                        .execute(
+                            "
+                    DELETE FROM score_achievement_rewards
+                    WHERE achievement = $1::INT
+                    ",
+                            &[&id],
+                        )
+                        .await?;
+                }
+            }
+
+            let achievement = Achievement {
+                id,
+                name,
+                criterion,
+                goal,
+                days,
+                role: role.as_ref().map(|role| role.id),
+            };
+
+            let reward = match role {
+                Some(role) => format!(
+                    "\nUsers unlocking it will receive the role {}.",
+                    role.mention()
+                ),
+                None => String::new(),
+            };
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                &format!(
+                    "The achievement **{}** can now be unlocked: {}.{}
+                    The progress of users is checked on their next score change.",
+                    achievement.name,
+                    achievement.description(),
+                    reward
+                ),
+            )
+            .await
+        }
+        Action::Remove => {
+            let modified = database
+                .client
+                .execute(
+                    "
+            DELETE FROM score_achievements
+            WHERE guild = $1::BIGINT AND name = $2::TEXT
+            ",
+                    &[&guild_db_id, &name],
+                )
+                .await?;
+
+            if modified == 0 {
+                send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    &format!(
+                        "There is no achievement called **{}**. I didn't remove anything.",
+                        name
+                    ),
+                )
+                .await
+            } else {
+                send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    &format!(
+                        "I have removed the achievement **{}**. \
+                        Reward roles which were already handed out are kept.",
+                        name
+                    ),
+                )
+                .await
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use itertools::Itertools;
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue::User,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    achievements::{get_progress, Criterion},
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    pluralize,
+    utils::{parse_arg_resolved, send_response},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse argument (use command user as fallback)
+    let user = if !options.is_empty() {
+        match parse_arg_resolved(options, 0)? {
+            User(user, ..) => user,
+            _ => unreachable!(),
+        }
+    } else {
+        &command.user
+    };
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get the achievements including the progress of the user
+    let progress = get_progress(&database, guild_id, user.id).await?;
+
+    let achievements = progress
+        .iter()
+        .map(|progress| {
+            let achievement = &progress.achievement;
+
+            let state = if progress.unlocked {
+                "unlocked".to_string()
+            } else {
+                match (achievement.criterion, progress.value) {
+                    (Criterion::Rank, Some(rank)) => format!("currently rank {}", rank),
+                    (Criterion::Rank, None) => "not ranked yet".to_string(),
+                    (_, value) => format!("{}/{}", value.unwrap_or_default(), achievement.goal),
+                }
+            };
+
+            let reward = match achievement.role {
+                Some(role) => format!(", rewards {}", role.mention()),
+                None => String::new(),
+            };
+
+            format!(
+                "**{}** ({}): {}{}, unlocked by {}",
+                achievement.name,
+                state,
+                achievement.description(),
+                reward,
+                pluralize!("user", progress.holders)
+            )
+        })
+        .join("\n");
+
+    let title = format!("Achievements of {}", user.name);
+
+    if achievements.is_empty() {
+        send_response(
+            ctx,
+            command,
+            command_config,
+            &title,
+            "There are currently no achievements defined for this server.",
+        )
+        .await
+    } else {
+        send_response(
+            ctx,
+            command,
+            command_config,
+            &title,
+            &format!(
+                "The following achievements can be unlocked on this server:
+                {}",
+                achievements
+            ),
+        )
+        .await
+    }
+}
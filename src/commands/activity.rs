@@ -0,0 +1,230 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue::Channel,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::{Command, Config},
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    pluralize,
+    strings::ERR_CMD_ARGS_INVALID,
+    utils::{parse_arg, parse_arg_resolved, send_response},
+};
+
+enum Action {
+    Enable,
+    Disable,
+    Exclude,
+    Include,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Action::Enable => "Enable",
+            Action::Disable => "Disable",
+            Action::Exclude => "Exclude channel from",
+            Action::Include => "Include channel in",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "enable" => Ok(Action::Enable),
+            "disable" => Ok(Action::Disable),
+            "exclude" => Ok(Action::Exclude),
+            "include" => Ok(Action::Include),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get config and database
+    let (config, database) = data!(ctx, (Config, Database));
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let action = Action::from_str(parse_arg(options, 0)?)?;
+    let mut cooldown = config.general.default_activity_cooldown;
+    let mut length = config.general.default_activity_length;
+    let mut channel = None;
+    for i in 1..options.len() {
+        match options.get(i).unwrap().name.as_str() {
+            "cooldown" => cooldown = parse_arg(options, i)?,
+            "length" => length = parse_arg(options, i)?,
+            "channel" => {
+                channel = match parse_arg_resolved(options, i)? {
+                    Channel(channel) => Some(channel),
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    let title = format!("{} message activity", action);
+
+    match action {
+        Action::Enable => {
+            // Insert or update entry
+            database
+                .client
+                .execute(
+                    "
+            INSERT INTO score_messages
+            VALUES ($1::BIGINT, $2::BIGINT, $3::INT)
+            ON CONFLICT (guild)
+            DO UPDATE SET cooldown = $2::BIGINT, length = $3::INT
+            ",
+                    &[&guild_db_id, &cooldown, &length],
+                )
+                .await?;
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                &format!(
+                    "Users will now receive a point for sending messages with at least {}, \
+                    at most once every {}.",
+                    pluralize!("character", length),
+                    pluralize!("second", cooldown)
+                ),
+            )
+            .await
+        }
+        Action::Disable => {
+            database
+                .client
+                .execute(
+                    "
+            DELETE FROM score_messages
+            WHERE guild = $1::BIGINT
+            ",
+                    &[&guild_db_id],
+                )
+                .await?;
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                "Users will no longer receive points for sending messages.
+                Points which were already awarded still count towards the score.",
+            )
+            .await
+        }
+        Action::Exclude | Action::Include => {
+            let channel = match channel {
+                Some(channel) => channel,
+                None => {
+                    return send_response(
+                        ctx,
+                        command,
+                        command_config,
+                        &title,
+                        "Please provide the channel you want to update.",
+                    )
+                    .await;
+                }
+            };
+
+            // Get channel id
+            let channel_db_id = database.get_channel(guild_id, channel.id).await?;
+
+            if matches!(action, Action::Exclude) {
+                database
+                    .client
+                    .execute(
+                        "
+                INSERT INTO score_message_exclusions
+                VALUES ($1::BIGINT, $2::BIGINT)
+                ON CONFLICT
+                DO NOTHING
+                ",
+                        &[&guild_db_id, &channel_db_id],
+                    )
+                    .await?;
+
+                send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    &format!(
+                        "Messages in {} will no longer be awarded.",
+                        channel.id.mention()
+                    ),
+                )
+                .await
+            } else {
+                let modified = database
+                    .client
+                    .execute(
+                        "
+                DELETE FROM score_message_exclusions
+                WHERE guild = $1::BIGINT AND channel = $2::BIGINT
+                ",
+                        &[&guild_db_id, &channel_db_id],
+                    )
+                    .await?;
+
+                if modified == 0 {
+                    send_response(
+                        ctx,
+                        command,
+                        command_config,
+                        &title,
+                        &format!(
+                            "The channel {} was not excluded. I didn't change anything.",
+                            channel.id.mention()
+                        ),
+                    )
+                    .await
+                } else {
+                    send_response(
+                        ctx,
+                        command,
+                        command_config,
+                        &title,
+                        &format!(
+                            "Messages in {} will now be awarded again.",
+                            channel.id.mention()
+                        ),
+                    )
+                    .await
+                }
+            }
+        }
+    }
+}
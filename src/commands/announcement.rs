@@ -0,0 +1,209 @@
+use chrono::Utc;
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    reminders::{ReminderScheduler, Repetition},
+    strings::ERR_CMD_ARGS_INVALID,
+    time_parser::{parse_time, time_hint, DATE_FORMAT},
+    utils::{parse_arg, parse_arg_resolved, send_response},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database and reminder scheduler
+    let (database, scheduler) = data!(ctx, (Database, ReminderScheduler));
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let channel = match parse_arg_resolved(options, 0)? {
+        ApplicationCommandInteractionDataOptionValue::Channel(channel) => channel,
+        _ => unreachable!(),
+    };
+    let message = parse_arg::<String>(options, 1)?;
+    let when = parse_arg::<String>(options, 2)?;
+    let mut role = None;
+    let mut title = None;
+    let mut image = None;
+    let mut repeat = None;
+    let mut until = None;
+    let mut count = None;
+    for i in 3..options.len() {
+        match options.get(i).unwrap().name.as_str() {
+            "role" => match parse_arg_resolved(options, i)? {
+                ApplicationCommandInteractionDataOptionValue::Role(value) => role = Some(value),
+                _ => unreachable!(),
+            },
+            "title" => title = Some(parse_arg::<String>(options, i)?),
+            "image" => image = Some(parse_arg::<String>(options, i)?),
+            "repeat" => repeat = Some(parse_arg::<String>(options, i)?),
+            "until" => until = Some(parse_arg::<String>(options, i)?),
+            "count" => count = Some(parse_arg::<i64>(options, i)?),
+            _ => unreachable!(),
+        }
+    }
+
+    // Check whether the author and the bot may post to the channel and mention the role
+    let guild_channel = channel
+        .id
+        .to_channel(ctx)
+        .await?
+        .guild()
+        .ok_or_else(|| DiscordApiError(ERR_CMD_ARGS_INVALID.to_string()))?;
+    let bot_id = ctx.cache.current_user_id();
+
+    // Discord resolves the permissions of the author in the chosen channel, including overwrites
+    let author_permissions = channel
+        .permissions
+        .or_else(|| {
+            command
+                .member
+                .as_ref()
+                .and_then(|member| member.permissions)
+        })
+        .unwrap_or_default();
+    let bot_permissions = guild_channel.permissions_for_user(ctx, bot_id)?;
+
+    for (permissions, subject) in [(author_permissions, "You"), (bot_permissions, "I")] {
+        if !permissions.send_messages() {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                "Schedule announcement",
+                &format!(
+                    "{} can't send messages in {}.",
+                    subject,
+                    channel.id.mention()
+                ),
+            )
+            .await;
+        }
+
+        if let Some(role) = &role {
+            if !role.mentionable && !permissions.mention_everyone() {
+                return send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    "Schedule announcement",
+                    &format!(
+                        "{} can't mention {} in {}.",
+                        subject,
+                        role.mention(),
+                        channel.id.mention()
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
+    // Get datetime of the announcement in the time zone of the author
+    let time_zone = database.get_time_zone(command.user.id).await?;
+    let now = Utc::now().with_timezone(&time_zone);
+    let datetime = match parse_time(&when, now).filter(|datetime| *datetime > now) {
+        Some(datetime) => datetime,
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                "Schedule announcement",
+                &time_hint(&when),
+            )
+            .await;
+        }
+    };
+
+    // Parse the repetition of the announcement
+    let repetition = match Repetition::parse(repeat, until, count, datetime, now) {
+        Ok(repetition) => repetition,
+        Err(why) => {
+            return send_response(ctx, command, command_config, "Schedule announcement", why).await;
+        }
+    };
+
+    // Get guild, channel and user ids, the announcement belongs to its author
+    let guild_id = command.guild_id.unwrap();
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let channel_db_id = database.get_channel(guild_id, channel.id).await?;
+    let user_db_id = database.get_user(guild_id, command.user.id).await?;
+    let role_db_id = match &role {
+        Some(role) => Some(database.get_role(guild_id, role.id).await?),
+        None => None,
+    };
+
+    // Add announcement to database
+    database
+        .client
+        .execute(
+            "
+    INSERT INTO reminders (guild, channel, \"user\", time, content, recurrence, ends, remaining,
+        announcement, role, title, image)
+    VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::TIMESTAMPTZ, $5::TEXT, $6::TEXT,
+        $7::TIMESTAMPTZ, $8::INT, TRUE, $9::BIGINT, $10::TEXT, $11::TEXT)
+    ",
+            &[
+                &guild_db_id,
+                &channel_db_id,
+                &user_db_id,
+                &datetime,
+                &message,
+                &repetition
+                    .as_ref()
+                    .map(|repetition| repetition.recurrence.to_string()),
+                &repetition.as_ref().and_then(|repetition| repetition.ends),
+                &repetition.as_ref().and_then(Repetition::remaining),
+                &role_db_id,
+                &title,
+                &image,
+            ],
+        )
+        .await?;
+    scheduler.schedule(datetime).await;
+
+    let mut content = format!(
+        "I'm going to post \"{}\" in {}",
+        message,
+        channel.id.mention()
+    );
+    if let Some(role) = &role {
+        content.push_str(&format!(" for {}", role.mention()));
+    }
+    content.push_str(&format!(
+        " on {} ({}, <t:{}:R>)!",
+        datetime.with_timezone(&time_zone).format(DATE_FORMAT),
+        time_zone.name(),
+        datetime.timestamp()
+    ));
+    if let Some(repetition) = repetition {
+        content.push_str(&format!(
+            "\n\nThe announcement {}.",
+            repetition.describe(&time_zone)
+        ));
+    }
+
+    send_response(
+        ctx,
+        command,
+        command_config,
+        "Schedule announcement",
+        &content,
+    )
+    .await
+}
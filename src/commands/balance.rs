@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue::User,
+    },
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    utils::{parse_arg_resolved, send_response_complex},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse argument (use command user as fallback)
+    let user = if !options.is_empty() {
+        match parse_arg_resolved(options, 0)? {
+            User(user, ..) => user,
+            _ => unreachable!(),
+        }
+    } else {
+        &command.user
+    };
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild and user ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let user_db_id = database.get_user(guild_id, user.id).await?;
+
+    // Get the balance of the user
+    let (earned, spent, balance) = database
+        .client
+        .query_opt(
+            "
+            SELECT earned, spent, balance FROM balances
+            WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
+            ",
+            &[&guild_db_id, &user_db_id],
+        )
+        .await?
+        .map_or((0, 0, 0), |row| {
+            (
+                row.get::<_, i64>(0),
+                row.get::<_, i64>(1),
+                row.get::<_, i64>(2),
+            )
+        });
+
+    // Get the last purchases of the user
+    let transactions = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT item, price, time FROM economy_transactions
+                WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
+                ORDER BY time DESC
+                LIMIT 5
+                ",
+                &[&guild_db_id, &user_db_id],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                format!(
+                    "**{}** for {} (<t:{}:R>)",
+                    row.get::<_, String>(0),
+                    row.get::<_, i64>(1),
+                    row.get::<_, DateTime<Utc>>(2).timestamp()
+                )
+            })
+            .join("\n")
+    };
+
+    send_response_complex(
+        ctx,
+        command,
+        command_config,
+        &format!("Balance of {}", user.name),
+        &format!(
+            "The user has a balance of **{}** [earned {}, spent {}].",
+            balance, earned, spent
+        ),
+        |embed| {
+            let transactions = if transactions.is_empty() {
+                "Not available".to_string()
+            } else {
+                transactions.clone()
+            };
+
+            embed.fields(vec![("Last purchases", transactions, false)])
+        },
+        Vec::new(),
+    )
+    .await
+}
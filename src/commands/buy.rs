@@ -0,0 +1,322 @@
+use std::time::Duration;
+
+use itertools::Itertools;
+use serenity::{
+    client::Context,
+    model::{id::RoleId, interactions::application_command::ApplicationCommandInteraction},
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::{Command, Config},
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    pluralize,
+    temporary_roles::add_temporary_role,
+    utils::{parse_arg, send_confirmation, send_response, InteractionResponse},
+};
+
+/// An item of the shop of a guild.
+struct Item {
+    name: String,
+    price: i64,
+    role: Option<RoleId>,
+    hours: Option<i64>,
+}
+
+impl Item {
+    fn describe(&self) -> String {
+        match (self.role, self.hours) {
+            (Some(role), Some(hours)) => {
+                format!(
+                    "the role {} for {}",
+                    role.mention(),
+                    pluralize!("hour", hours)
+                )
+            }
+            (Some(role), None) => format!("the role {}", role.mention()),
+            _ => format!("the item **{}**", self.name),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get config and database
+    let (config, database) = data!(ctx, (Config, Database));
+
+    let options = &command.data.options;
+
+    // Parse argument
+    let name: Option<String> = if !options.is_empty() {
+        Some(parse_arg(options, 0)?)
+    } else {
+        None
+    };
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild and user ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let user_db_id = database.get_user(guild_id, command.user.id).await?;
+
+    // Get the balance of the user
+    let balance = database
+        .client
+        .query_opt(
+            "
+            SELECT balance FROM balances
+            WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
+            ",
+            &[&guild_db_id, &user_db_id],
+        )
+        .await?
+        .map_or(0, |row| row.get::<_, i64>(0));
+
+    // Get the items of the shop
+    let items: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT name, price, role, hours FROM economy_items
+                WHERE guild = $1::BIGINT
+                ORDER BY price, name
+                ",
+                &[&guild_db_id],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| Item {
+                name: row.get(0),
+                price: row.get(1),
+                role: row.get::<_, Option<i64>>(2).map(|role| RoleId(role as u64)),
+                hours: row.get(3),
+            })
+            .collect()
+    };
+
+    // List the items if no item was given
+    let name = match name {
+        Some(name) => name,
+        None => {
+            let title = "Shop";
+
+            if items.is_empty() {
+                return send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    title,
+                    "There are currently no items in the shop of this server.",
+                )
+                .await;
+            }
+
+            let items = items
+                .iter()
+                .map(|item| format!("**{}** ({}): {}", item.name, item.price, item.describe()))
+                .join("\n");
+
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                title,
+                &format!(
+                    "Your balance is **{}**. The following items can be bought:
+                    {}",
+                    balance, items
+                ),
+            )
+            .await;
+        }
+    };
+
+    let title = format!("Buy {}", name);
+
+    let item = match items.into_iter().find(|item| item.name == name) {
+        Some(item) => item,
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                &format!("There is no item called **{}** in the shop.", name),
+            )
+            .await;
+        }
+    };
+
+    // Permanent roles can't be bought twice
+    let mut member = guild_id.member(&ctx, command.user.id).await?;
+    if let (Some(role), None) = (item.role, item.hours) {
+        if member.roles.contains(&role) {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                &format!("You already have the role {}.", role.mention()),
+            )
+            .await;
+        }
+    }
+
+    if balance < item.price {
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            &title,
+            &format!(
+                "Your balance of **{}** is not sufficient to buy {} at a price of {}.",
+                balance,
+                item.describe(),
+                item.price
+            ),
+        )
+        .await;
+    }
+
+    // Check for the interaction response
+    let response = send_confirmation(
+        ctx,
+        command,
+        command_config,
+        &format!(
+            "Are you really sure you want to buy {} at a price of {}?",
+            item.describe(),
+            item.price
+        ),
+        Duration::from_secs(config.general.interaction_timeout),
+    )
+    .await?;
+
+    match response {
+        Some(InteractionResponse::Continue) => {
+            // Charge the user if the balance is still sufficient, locking the row of the user
+            database
+                .client
+                .execute(
+                    "
+                    INSERT INTO economy_balances (guild, \"user\")
+                    VALUES ($1::BIGINT, $2::BIGINT)
+                    ON CONFLICT
+                    DO NOTHING
+                    ",
+                    &[&guild_db_id, &user_db_id],
+                )
+                .await?;
+            let balance = database
+                .client
+                .query_opt(
+                    "
+                    UPDATE economy_balances eb
+                    SET spent = eb.spent + $3::BIGINT
+                    FROM balances b
+                    WHERE eb.guild = $1::BIGINT AND eb.\"user\" = $2::BIGINT
+                        AND b.guild = eb.guild AND b.\"user\" = eb.\"user\"
+                        AND eb.spent + $3::BIGINT <= b.earned
+                    RETURNING b.earned - eb.spent
+                    ",
+                    &[&guild_db_id, &user_db_id, &item.price],
+                )
+                .await?
+                .map(|row| row.get::<_, i64>(0));
+
+            let balance = match balance {
+                Some(balance) => balance,
+                None => {
+                    return send_response(
+                        ctx,
+                        command,
+                        command_config,
+                        &title,
+                        "Your balance is no longer sufficient to buy this item.",
+                    )
+                    .await;
+                }
+            };
+
+            // Hand out the role
+            if let Some(role) = item.role {
+                if let Err(why) = member.add_role(&ctx.http, role).await {
+                    // Refund the user if the role couldn't be assigned
+                    database
+                        .client
+                        .execute(
+                            "
+                            UPDATE economy_balances
+                            SET spent = spent - $3::BIGINT
+                            WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
+                            ",
+                            &[&guild_db_id, &user_db_id, &item.price],
+                        )
+                        .await?;
+
+                    return Err(why.into());
+                }
+
+                if let Some(hours) = item.hours {
+                    add_temporary_role(&database, guild_id, command.user.id, role, hours).await?;
+                }
+
+                // Get role id
+                let role_db_id = database.get_role(guild_id, role).await?;
+
+                database
+                    .client
+                    .execute(
+                        "
+                        INSERT INTO economy_transactions (guild, \"user\", item, role, price, time)
+                        VALUES ($1::BIGINT, $2::BIGINT, $3::TEXT, $4::BIGINT, $5::BIGINT, NOW())
+                        ",
+                        &[
+                            &guild_db_id,
+                            &user_db_id,
+                            &item.name,
+                            &role_db_id,
+                            &item.price,
+                        ],
+                    )
+                    .await?;
+            } else {
+                database
+                    .client
+                    .execute(
+                        "
+                        INSERT INTO economy_transactions (guild, \"user\", item, price, time)
+                        VALUES ($1::BIGINT, $2::BIGINT, $3::TEXT, $4::BIGINT, NOW())
+                        ",
+                        &[&guild_db_id, &user_db_id, &item.name, &item.price],
+                    )
+                    .await?;
+            }
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                &format!(
+                    "You have bought {} for {}. Your new balance is **{}**.",
+                    item.describe(),
+                    item.price,
+                    balance
+                ),
+            )
+            .await
+        }
+        Some(InteractionResponse::Abort) => {
+            send_response(ctx, command, command_config, &title, "Aborted the action.").await
+        }
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,46 @@
+use serenity::{
+    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    utils::{parse_arg, send_response},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse argument
+    let enabled = parse_arg::<bool>(options, 0)?;
+
+    database
+        .client
+        .execute(
+            "
+            INSERT INTO user_settings (\"user\", event_reminders)
+            VALUES ($1::BIGINT, $2::BOOL)
+            ON CONFLICT (\"user\")
+            DO UPDATE SET event_reminders = $2::BOOL
+            ",
+            &[&(command.user.id.0 as i64), &enabled],
+        )
+        .await?;
+
+    let content = if enabled {
+        "From now on, I'm going to remind you about the events you're interested in."
+    } else {
+        "From now on, I won't remind you about events anymore."
+    };
+
+    send_response(ctx, command, command_config, "Event reminders", content).await
+}
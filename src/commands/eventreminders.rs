@@ -0,0 +1,198 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use itertools::Itertools;
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    event_reminders::EventReminderScheduler,
+    pluralize,
+    strings::ERR_CMD_ARGS_INVALID,
+    time_parser::parse_duration,
+    utils::{parse_arg, parse_arg_resolved, send_response},
+};
+
+/// Minutes before the start of an event to send reminders at if none are given.
+const DEFAULT_MINUTES: [i32; 2] = [60, 15];
+
+enum Action {
+    Enable,
+    Disable,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Action::Enable => "Enable",
+            Action::Disable => "Disable",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "enable" => Ok(Action::Enable),
+            "disable" => Ok(Action::Disable),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database and event reminder scheduler
+    let (database, scheduler) = data!(ctx, (Database, EventReminderScheduler));
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let action = Action::from_str(parse_arg(options, 0)?)?;
+    let mut before = None;
+    let mut channel = None;
+    for i in 1..options.len() {
+        match options.get(i).unwrap().name.as_str() {
+            "before" => before = Some(parse_arg::<String>(options, i)?),
+            "channel" => match parse_arg_resolved(options, i)? {
+                ApplicationCommandInteractionDataOptionValue::Channel(value) => {
+                    channel = Some(value)
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    let title = format!("{} event reminders", action);
+
+    match action {
+        Action::Enable => {
+            // Parse the offsets before the start of events, e.g. "1d, 1h, 15m"
+            let minutes = match before {
+                Some(before) => {
+                    let minutes: Option<Vec<i32>> = before
+                        .split(',')
+                        .map(|offset| {
+                            parse_duration(offset.trim())
+                                .map(|duration| duration.num_minutes() as i32)
+                                .filter(|minutes| *minutes > 0)
+                        })
+                        .collect();
+
+                    match minutes {
+                        Some(minutes) => minutes.into_iter().sorted().rev().dedup().collect(),
+                        None => {
+                            return send_response(
+                                ctx,
+                                command,
+                                command_config,
+                                &title,
+                                &format!(
+                                    "I don't understand \"{}\". Try something like \"1d, 1h, 15m\".",
+                                    before
+                                ),
+                            )
+                            .await;
+                        }
+                    }
+                }
+                None => DEFAULT_MINUTES.to_vec(),
+            };
+
+            let channel_db_id = match &channel {
+                Some(channel) => Some(database.get_channel(guild_id, channel.id).await?),
+                None => None,
+            };
+
+            database
+                .client
+                .execute(
+                    "
+                INSERT INTO event_reminder_settings
+                VALUES ($1::BIGINT, $2::INT[], $3::BIGINT)
+                ON CONFLICT (guild)
+                DO UPDATE SET minutes = $2::INT[], channel = $3::BIGINT
+                ",
+                    &[&guild_db_id, &minutes, &channel_db_id],
+                )
+                .await?;
+
+            // Wake the scheduler, as the offsets might have changed
+            scheduler.reschedule();
+
+            let offsets = minutes
+                .iter()
+                .map(|minutes| describe_minutes(*minutes))
+                .join(", ");
+            let target = match channel {
+                Some(channel) => format!("mention them in {}", channel.id.mention()),
+                None => "send them a direct message".to_string(),
+            };
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                &format!(
+                    "I'm going to remind users interested in events {} before the start. \
+                    For that, I'll {}.",
+                    offsets, target
+                ),
+            )
+            .await
+        }
+        Action::Disable => {
+            database
+                .client
+                .execute(
+                    "DELETE FROM event_reminder_settings WHERE guild = $1::BIGINT",
+                    &[&guild_db_id],
+                )
+                .await?;
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                "I'm not going to remind users about the events of this guild anymore.",
+            )
+            .await
+        }
+    }
+}
+
+// Describe an amount of minutes using the largest fitting unit
+fn describe_minutes(minutes: i32) -> String {
+    match minutes {
+        _ if minutes % 1440 == 0 => pluralize!("day", minutes / 1440),
+        _ if minutes % 60 == 0 => pluralize!("hour", minutes / 60),
+        _ => pluralize!("minute", minutes),
+    }
+}
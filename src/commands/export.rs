@@ -0,0 +1,66 @@
+use std::borrow::Cow;
+
+use serenity::{
+    client::Context,
+    model::{
+        channel::AttachmentType, interactions::application_command::ApplicationCommandInteraction,
+    },
+};
+
+use crate::{
+    config::Command, data, database::client::Database, error::KowalskiError,
+    guild_config::export_config, pluralize, utils::send_response,
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let title = "Export configuration";
+
+    let guild_id = match command.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                title,
+                "Configurations can only be exported on guilds.",
+            )
+            .await;
+        }
+    };
+
+    // Export the configuration referring to roles, channels and emojis by name
+    let config = export_config(ctx, &database, guild_id).await?;
+    let json = serde_json::to_string_pretty(&config)?;
+
+    let mut content = "I have exported the module configuration of this guild. \
+        Use `/import` on another guild to apply it there."
+        .to_string();
+    if !config.reaction_roles.is_empty() {
+        content.push_str(&format!(
+            "\n\nThe reaction-roles of {} not composed by the bot can only be imported on this guild.",
+            pluralize!("message", config.reaction_roles.len())
+        ));
+    }
+
+    send_response(ctx, command, command_config, title, &content).await?;
+
+    // Attach the configuration file
+    command
+        .create_followup_message(&ctx.http, |message| {
+            message.add_file(AttachmentType::Bytes {
+                data: Cow::from(json.into_bytes()),
+                filename: format!("{}.json", guild_id.0),
+            })
+        })
+        .await?;
+
+    Ok(())
+}
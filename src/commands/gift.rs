@@ -7,13 +7,16 @@ use serenity::{
     },
     prelude::Mentionable,
 };
+use tracing::error;
 
 use crate::{
+    achievements::check_achievements,
     config::{Command, Config},
     data,
     database::client::Database,
     error::KowalskiError,
     pluralize,
+    strings::ERR_ACHIEVEMENTS,
     utils::{parse_arg, parse_arg_resolved, send_confirmation, send_response, InteractionResponse},
 };
 
@@ -119,6 +122,14 @@ pub async fn execute(
                 )
                 .await?;
 
+            // Check the achievements of both users
+            if let Err(why) = check_achievements(ctx, &database, guild_id, command.user.id).await {
+                error!("{}: {:?}", ERR_ACHIEVEMENTS, why);
+            }
+            if let Err(why) = check_achievements(ctx, &database, guild_id, user.id).await {
+                error!("{}: {:?}", ERR_ACHIEVEMENTS, why);
+            }
+
             send_response(
                 ctx,
                 command,
@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue::Attachment,
+    },
+};
+
+use crate::{
+    config::{Command, Config},
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    guild_config::{apply_import, plan_import, GuildConfig},
+    utils::{parse_arg_resolved, send_confirmation, send_response, InteractionResponse},
+};
+
+/// Maximum number of characters of the change list shown in the confirmation.
+const MAX_DIFF_LENGTH: usize = 3500;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get config and database
+    let (config, database) = data!(ctx, (Config, Database));
+
+    let options = &command.data.options;
+
+    // Parse argument
+    let attachment = match parse_arg_resolved(options, 0)? {
+        Attachment(attachment) => attachment,
+        _ => unreachable!(),
+    };
+
+    let title = "Import configuration";
+
+    let guild_id = match command.guild_id {
+        Some(guild_id) => guild_id,
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                title,
+                "Configurations can only be imported on guilds.",
+            )
+            .await;
+        }
+    };
+
+    // Download and parse the configuration
+    let imported: Option<GuildConfig> = match attachment.download().await {
+        Ok(bytes) => serde_json::from_slice(&bytes).ok(),
+        Err(_) => None,
+    };
+    let imported = match imported {
+        Some(imported) => imported,
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                title,
+                "I couldn't read the file. Is it a configuration created by `/export`?",
+            )
+            .await;
+        }
+    };
+
+    // Map the configuration onto this guild
+    let import = plan_import(ctx, &database, guild_id, imported).await?;
+
+    if import.changes.iter().all(|change| change.starts_with('=')) {
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            title,
+            &format!(
+                "There is nothing to import, this guild is already configured this way.{}",
+                if import.skipped.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        "\n\nThe following entries couldn't be mapped:\n{}",
+                        import.skipped.join("\n")
+                    )
+                }
+            ),
+        )
+        .await;
+    }
+
+    // Show the changes before applying them
+    let mut diff = format!(
+        "Importing the configuration makes the following changes \
+        (+ added, ~ changed, = unchanged):\n```diff\n{}\n```",
+        truncate(&import.changes)
+    );
+    if !import.skipped.is_empty() {
+        diff.push_str(&format!(
+            "\nThe following entries couldn't be mapped onto this guild and will be skipped:\n```\n{}\n```",
+            truncate(&import.skipped)
+        ));
+    }
+
+    let response = send_confirmation(
+        ctx,
+        command,
+        command_config,
+        &diff,
+        Duration::from_secs(config.general.interaction_timeout),
+    )
+    .await?;
+
+    match response {
+        Some(InteractionResponse::Continue) => {
+            apply_import(ctx, &database, guild_id, import).await?;
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                title,
+                "I have imported the configuration. Existing entries not part of it were kept.",
+            )
+            .await
+        }
+        Some(InteractionResponse::Abort) => {
+            send_response(ctx, command, command_config, title, "Aborted the action.").await
+        }
+        None => Ok(()),
+    }
+}
+
+// Join lines while staying within the embed limits
+fn truncate(lines: &[String]) -> String {
+    let mut result = String::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        if result.len() + line.len() > MAX_DIFF_LENGTH / 2 {
+            result.push_str(&format!("... and {} more", lines.len() - i));
+            break;
+        }
+
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    result.trim_end().to_string()
+}
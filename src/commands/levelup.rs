@@ -17,6 +17,7 @@ use crate::{
     database::client::Database,
     error::KowalskiError,
     error::KowalskiError::DiscordApiError,
+    pluralize,
     strings::ERR_CMD_ARGS_INVALID,
     utils::{parse_arg, parse_arg_resolved, send_response},
 };
@@ -66,6 +67,13 @@ pub async fn execute(
         _ => unreachable!(),
     };
     let score: i64 = parse_arg(options, 2)?;
+    let hours = {
+        if options.len() > 3 {
+            Some(parse_arg::<i64>(options, 3)?)
+        } else {
+            None
+        }
+    };
 
     // Get guild and role ids
     let guild_db_id = database.get_guild(role.guild_id).await?;
@@ -80,11 +88,11 @@ pub async fn execute(
                 .execute(
                     "
             INSERT INTO score_roles
-            VALUES($1::BIGINT, $2::BIGINT, $3::BIGINT)
-            ON CONFLICT
-            DO NOTHING
+            VALUES($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::BIGINT)
+            ON CONFLICT (guild, role, score)
+            DO UPDATE SET hours = $4::BIGINT
             ",
-                    &[&guild_db_id, &role_db_id, &score],
+                    &[&guild_db_id, &role_db_id, &score, &hours],
                 )
                 .await?;
 
@@ -94,9 +102,13 @@ pub async fn execute(
                 command_config,
                 &title,
                 &format!(
-                    "Users reaching a score of {} will now receive the role {}.",
+                    "Users reaching a score of {} will now receive the role {}{}.",
                     score,
-                    role.mention()
+                    role.mention(),
+                    hours.map_or(String::new(), |hours| format!(
+                        " for {}",
+                        pluralize!("hour", hours)
+                    ))
                 ),
             )
             .await
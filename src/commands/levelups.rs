@@ -6,7 +6,8 @@ use serenity::{
 };
 
 use crate::{
-    config::Command, data, database::client::Database, error::KowalskiError, utils::send_response,
+    config::Command, data, database::client::Database, error::KowalskiError, pluralize,
+    utils::send_response,
 };
 
 pub async fn execute(
@@ -28,7 +29,7 @@ pub async fn execute(
             .client
             .query(
                 "
-                SELECT role, score FROM score_roles
+                SELECT role, score, hours FROM score_roles
                 WHERE guild = $1::BIGINT
                 ORDER BY score
                 ",
@@ -37,18 +38,28 @@ pub async fn execute(
             .await?;
 
         rows.iter()
-            .map(|row| (RoleId(row.get::<_, i64>(0) as u64), row.get::<_, i64>(1)))
+            .map(|row| {
+                (
+                    RoleId(row.get::<_, i64>(0) as u64),
+                    row.get::<_, i64>(1),
+                    row.get::<_, Option<i64>>(2),
+                )
+            })
             .collect()
     };
 
     let levelup_roles = role_cooldowns
         .iter()
-        .map(|&(role_id, cooldown)| {
+        .map(|&(role_id, cooldown, hours)| {
             format!(
-                "{}: **score {} {}**",
+                "{}: **score {} {}**{}",
                 role_id.mention(),
                 if cooldown >= 0 { ">=" } else { "<=" },
-                cooldown
+                cooldown,
+                hours.map_or(String::new(), |hours| format!(
+                    " (for {})",
+                    pluralize!("hour", hours)
+                ))
             )
         })
         .join("\n");
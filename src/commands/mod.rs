@@ -1,4 +1,10 @@
 pub mod about;
+pub mod achievement;
+pub mod achievements;
+pub mod activity;
+pub mod announcement;
+pub mod balance;
+pub mod buy;
 pub mod clean;
 pub mod clear;
 pub mod cooldown;
@@ -8,11 +14,15 @@ pub mod drop;
 pub mod drops;
 pub mod emoji;
 pub mod emojis;
+pub mod eventnotifications;
+pub mod eventreminders;
+pub mod export;
 pub mod gift;
 pub mod given;
 pub mod givens;
 pub mod global;
 pub mod guild;
+pub mod import;
 pub mod levelup;
 pub mod levelups;
 pub mod moderation;
@@ -21,18 +31,34 @@ pub mod module;
 pub mod modules;
 #[cfg(feature = "nlp-model")]
 pub mod mood;
+pub mod myreminders;
+pub mod nickname;
 #[cfg(feature = "nlp-model")]
 pub mod oracle;
 pub mod ping;
 #[cfg(feature = "event-calendar")]
 pub mod publish;
+pub mod rate;
+pub mod reactiongroup;
+pub mod reactionmessage;
+pub mod reactionrequirement;
 pub mod reactionrole;
 pub mod reactionroles;
+pub mod reactionslots;
 pub mod reminder;
+pub mod reminderdelivery;
 pub mod reminders;
+pub mod remindmessage;
+pub mod rolemenu;
 pub mod say;
 pub mod score;
 pub mod scores;
+pub mod shop;
 pub mod sql;
+pub mod temprole;
+pub mod temproles;
+pub mod timezone;
 #[cfg(feature = "nlp-model")]
 pub mod tldr;
+pub mod voice;
+pub mod weight;
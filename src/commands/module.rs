@@ -15,6 +15,7 @@ use crate::{
     database::{client::Database, types::ModuleStatus},
     error::KowalskiError,
     error::KowalskiError::DiscordApiError,
+    nicknames::restore_nicknames,
     strings::ERR_CMD_ARGS_INVALID,
     utils::{
         create_module_command, parse_arg, send_confirmation, send_failure, send_response,
@@ -151,6 +152,8 @@ pub async fn execute(
             Module::Score => status_new.score = enable,
             Module::ReactionRoles => status_new.reaction_roles = enable,
             Module::Analyze => status_new.analyze = enable,
+            Module::Voice => status_new.voice = enable,
+            Module::Economy => status_new.economy = enable,
         };
 
         // Check whether the status has changed
@@ -263,47 +266,86 @@ async fn remove(
 
     match module {
         Module::Utility => {
-            database
-                .client
-                .execute(
-                    "
-                    DELETE FROM publishing WHERE guild = $1::BIGINT;
-
-                    DELETE FROM reminders WHERE guild = $1::BIGINT;
-                    ",
-                    &[&guild_db_id],
-                )
-                .await?;
+            delete_guild_data(
+                &database,
+                guild_db_id,
+                &[
+                    "DELETE FROM publishing WHERE guild = $1::BIGINT",
+                    "DELETE FROM reminders WHERE guild = $1::BIGINT",
+                    "DELETE FROM event_reminder_settings WHERE guild = $1::BIGINT",
+                    "DELETE FROM event_reminders_sent WHERE guild = $1::BIGINT",
+                    "DELETE FROM temporary_roles WHERE guild = $1::BIGINT",
+                    "DELETE FROM scheduled_events WHERE guild = $1::BIGINT",
+                ],
+            )
+            .await?;
         }
         Module::Score => {
-            database
-                .client
-                .execute(
-                    "
-                    DELETE FROM score_auto_delete WHERE guild = $1::BIGINT;
-
-                    DELETE FROM score_auto_pin WHERE guild = $1::BIGINT;
-
-                    DELETE FROM score_cooldowns WHERE guild = $1::BIGINT;
-
-                    DELETE FROM score_drops WHERE guild = $1::BIGINT;
-
-                    DELETE FROM score_emojis WHERE guild = $1::BIGINT;
-
-                    DELETE FROM score_roles WHERE guild = $1::BIGINT;
-                    ",
-                    &[&guild_db_id],
-                )
-                .await?;
+            // Restore the nicknames before their originals get lost
+            restore_nicknames(ctx, &database, guild_id).await?;
+
+            delete_guild_data(
+                &database,
+                guild_db_id,
+                &[
+                    "DELETE FROM score_auto_delete WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_auto_pin WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_cooldowns WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_drops WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_emojis WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_roles WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_role_grants WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_messages WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_message_exclusions WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_message_activity WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_weights WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_pickups WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_achievements WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_nicknames WHERE guild = $1::BIGINT",
+                    "DELETE FROM score_nickname_originals WHERE guild = $1::BIGINT",
+                ],
+            )
+            .await?;
         }
         Module::ReactionRoles => {
-            database
-                .client
-                .execute(
-                    "DELETE FROM reminders WHERE guild = $1::BIGINT",
-                    &[&guild_db_id],
-                )
-                .await?;
+            delete_guild_data(
+                &database,
+                guild_db_id,
+                &[
+                    "DELETE FROM reaction_roles WHERE guild = $1::BIGINT",
+                    "DELETE FROM reaction_role_groups WHERE guild = $1::BIGINT",
+                    "DELETE FROM role_menu_roles WHERE guild = $1::BIGINT",
+                    "DELETE FROM role_menus WHERE guild = $1::BIGINT",
+                    "DELETE FROM reaction_role_requirements WHERE guild = $1::BIGINT",
+                    "DELETE FROM reaction_role_waitlist WHERE guild = $1::BIGINT",
+                    "DELETE FROM reaction_role_messages WHERE guild = $1::BIGINT",
+                ],
+            )
+            .await?;
+        }
+        Module::Voice => {
+            delete_guild_data(
+                &database,
+                guild_db_id,
+                &[
+                    "DELETE FROM voice_activity WHERE guild = $1::BIGINT",
+                    "DELETE FROM voice_rates WHERE guild = $1::BIGINT",
+                ],
+            )
+            .await?;
+        }
+        Module::Economy => {
+            delete_guild_data(
+                &database,
+                guild_db_id,
+                &[
+                    "DELETE FROM economy_rates WHERE guild = $1::BIGINT",
+                    "DELETE FROM economy_items WHERE guild = $1::BIGINT",
+                    "DELETE FROM economy_balances WHERE guild = $1::BIGINT",
+                    "DELETE FROM economy_transactions WHERE guild = $1::BIGINT",
+                ],
+            )
+            .await?;
         }
         _ => {
             return send_response(
@@ -326,3 +368,16 @@ async fn remove(
     )
     .await
 }
+
+/// Delete the data of a guild, running each of the given queries with the guild id as parameter.
+async fn delete_guild_data(
+    database: &Database,
+    guild_db_id: i64,
+    queries: &[&str],
+) -> Result<(), KowalskiError> {
+    for query in queries {
+        database.client.execute(*query, &[&guild_db_id]).await?;
+    }
+
+    Ok(())
+}
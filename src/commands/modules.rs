@@ -73,6 +73,13 @@ pub async fn execute(
         case, the bot will only assign the reaction-role to users as long as there are slots available.",
             Module::Analyze => "The analyze module provides commands to analyze previous messages written \
         in a specific channel.",
+            Module::Voice => "The voice module tracks the time users spend talking in voice channels. \
+        Only non-AFK channels with at least two unmuted users count. Once a conversion rate is set, \
+        the voice activity gets converted to score, counting towards the level-up roles and the \
+        leaderboards of the score module.",
+            Module::Economy => "The economy module converts the upvotes users have received into a \
+        spendable balance. Users can spend their balance in the shop of the server to buy roles, \
+        temporary roles or custom items. Spending the balance does not affect the score.",
         };
 
         // Check whether the current module is enabled
@@ -82,6 +89,8 @@ pub async fn execute(
             Module::Score => status.score,
             Module::ReactionRoles => status.reaction_roles,
             Module::Analyze => status.analyze,
+            Module::Voice => status.voice,
+            Module::Economy => status.economy,
         };
 
         fields.push((
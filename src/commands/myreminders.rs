@@ -0,0 +1,151 @@
+use chrono::{DateTime, Utc};
+use serenity::{
+    client::Context,
+    model::{
+        id::{ChannelId, GuildId},
+        interactions::application_command::ApplicationCommandInteraction,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::Command,
+    config::Config,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    reminders::describe_repetition,
+    time_parser::DATE_FORMAT,
+    utils::{parse_arg, send_response, send_response_complex},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get config and database
+    let (config, database) = data!(ctx, (Config, Database));
+
+    let options = &command.data.options;
+
+    let title = "Your reminders";
+
+    // Cancel the given reminder if it belongs to the user
+    if !options.is_empty() {
+        let id = parse_arg::<i64>(options, 0)? as i32;
+
+        let deleted = database
+            .client
+            .execute(
+                "DELETE FROM reminders WHERE id = $1::INT AND \"user\" = $2::BIGINT",
+                &[&id, &(command.user.id.0 as i64)],
+            )
+            .await?;
+
+        let content = if deleted > 0 {
+            format!("I've cancelled the reminder `#{}`.", id)
+        } else {
+            format!("You don't have a reminder with the id `#{}`.", id)
+        };
+
+        return send_response(ctx, command, command_config, title, &content).await;
+    }
+
+    // Show the reminders in the time zone of the user
+    let time_zone = database.get_time_zone(command.user.id).await?;
+
+    // Get the reminders of the user across all guilds and direct messages
+    let rows = database
+        .client
+        .query(
+            "
+            SELECT id, guild, channel, time, content, recurrence, ends, remaining
+            FROM reminders
+            WHERE \"user\" = $1::BIGINT
+            ORDER BY time
+            ",
+            &[&(command.user.id.0 as i64)],
+        )
+        .await?;
+
+    if rows.is_empty() {
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            title,
+            "Looks like you don't have any pending reminders :(",
+        )
+        .await;
+    }
+
+    let size = config.general.reminder_list_size;
+    let content = if rows.len() > size {
+        format!(
+            "Showing the next {} of your {} reminders. \
+            Cancel a reminder using its id to see more.",
+            size,
+            rows.len()
+        )
+    } else {
+        String::new()
+    };
+
+    let fields: Vec<_> = rows
+        .iter()
+        .take(size)
+        .map(|row| {
+            let id = row.get::<_, i32>(0);
+            let guild_id = row
+                .get::<_, Option<i64>>(1)
+                .map(|guild| GuildId(guild as u64));
+            let channel_id = ChannelId(row.get::<_, i64>(2) as u64);
+            let time = row.get::<_, DateTime<Utc>>(3);
+            // Cut of content after a certain length
+            let content = row
+                .get::<_, String>(4)
+                .chars()
+                .take(config.general.reminder_list_max_message_length)
+                .collect::<String>();
+
+            let location = match guild_id {
+                Some(guild_id) => format!(
+                    "{} on {}",
+                    channel_id.mention(),
+                    guild_id
+                        .name(ctx)
+                        .unwrap_or_else(|| "an unknown guild".to_string())
+                ),
+                None => "a direct message".to_string(),
+            };
+
+            let mut value = format!("In {}: {}", location, content);
+            if let Some(repetition) = describe_repetition(row, 5, &time_zone) {
+                value.push_str(&format!("\n*{}*", repetition));
+            }
+
+            (
+                format!(
+                    "`#{}` {} ({})",
+                    id,
+                    time.with_timezone(&time_zone).format(DATE_FORMAT),
+                    time_zone.name()
+                ),
+                value,
+                false,
+            )
+        })
+        .collect();
+
+    send_response_complex(
+        ctx,
+        command,
+        command_config,
+        title,
+        &content,
+        |embed| embed.fields(fields.clone()),
+        Vec::new(),
+    )
+    .await
+}
@@ -0,0 +1,107 @@
+use serenity::{
+    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    nicknames::{restore_nicknames, update_nicknames},
+    utils::{parse_arg, send_response},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let mut source: Option<String> = None;
+    let mut suffix = false;
+    for i in 0..options.len() {
+        match options.get(i).unwrap().name.as_str() {
+            "source" => source = Some(parse_arg(options, i)?),
+            "position" => suffix = parse_arg::<String>(options, i)? == "suffix",
+            _ => unreachable!(),
+        }
+    }
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    let title = "Set nickname tags";
+
+    // Restore the nicknames tagged with the previous settings
+    restore_nicknames(ctx, &database, guild_id).await?;
+
+    match source {
+        Some(source) => {
+            // Insert or update entry
+            database
+                .client
+                .execute(
+                    "
+            INSERT INTO score_nicknames
+            VALUES ($1::BIGINT, $2::TEXT, $3::BOOLEAN)
+            ON CONFLICT (guild)
+            DO UPDATE SET source = $2::TEXT, suffix = $3::BOOLEAN, ranking = '{}'
+            ",
+                    &[&guild_db_id, &source, &suffix],
+                )
+                .await?;
+
+            // Tag the current leaders right away
+            update_nicknames(ctx, &database, guild_id, command.user.id).await?;
+
+            let source = if source == "rank" {
+                "their position on the leaderboard"
+            } else {
+                "the name of their highest level-up role"
+            };
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                title,
+                &format!(
+                    "The nicknames of users will now be tagged with {} as a {}.
+                    Users the bot can't rename will keep their nickname.",
+                    source,
+                    if suffix { "suffix" } else { "prefix" }
+                ),
+            )
+            .await
+        }
+        None => {
+            database
+                .client
+                .execute(
+                    "
+            DELETE FROM score_nicknames
+            WHERE guild = $1::BIGINT
+            ",
+                    &[&guild_db_id],
+                )
+                .await?;
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                title,
+                "The nicknames of users will no longer be tagged. \
+                I have restored the original nicknames.",
+            )
+            .await
+        }
+    }
+}
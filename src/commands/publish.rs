@@ -5,7 +5,14 @@ use std::{
 
 use rand::Rng;
 use serenity::{
-    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+    client::Context,
+    model::{
+        id::{ChannelId, UserId},
+        interactions::application_command::{
+            ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue,
+        },
+    },
+    prelude::Mentionable,
 };
 
 use crate::{
@@ -15,19 +22,23 @@ use crate::{
     error::KowalskiError,
     error::KowalskiError::DiscordApiError,
     strings::ERR_CMD_ARGS_INVALID,
-    utils::{parse_arg, send_response},
+    utils::{parse_arg, parse_arg_resolved, send_response, send_response_complex},
 };
 
 enum Action {
-    Enable,
-    Disable,
+    List,
+    Create,
+    Rotate,
+    Delete,
 }
 
 impl Display for Action {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let name = match self {
-            Action::Enable => "Enable",
-            Action::Disable => "Disable",
+            Action::List => "List",
+            Action::Create => "Create",
+            Action::Rotate => "Rotate",
+            Action::Delete => "Delete",
         };
 
         write!(f, "{}", name)
@@ -39,8 +50,10 @@ impl FromStr for Action {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
-            "enable" => Ok(Action::Enable),
-            "disable" => Ok(Action::Disable),
+            "list" => Ok(Action::List),
+            "create" => Ok(Action::Create),
+            "rotate" => Ok(Action::Rotate),
+            "delete" => Ok(Action::Delete),
             _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
         }
     }
@@ -58,110 +71,234 @@ pub async fn execute(
 
     // Parse arguments
     let action = Action::from_str(parse_arg(options, 0)?).unwrap();
+    let mut name = None;
+    let mut channel_id = None;
+    let mut keyword = None;
+    let mut creator_id = None;
+    for i in 1..options.len() {
+        match options.get(i).unwrap().name.as_str() {
+            "name" => name = Some(parse_arg::<String>(options, i)?),
+            "channel" => match parse_arg_resolved(options, i)? {
+                ApplicationCommandInteractionDataOptionValue::Channel(channel) => {
+                    channel_id = Some(channel.id)
+                }
+                _ => unreachable!(),
+            },
+            "keyword" => keyword = Some(parse_arg::<String>(options, i)?),
+            "creator" => match parse_arg_resolved(options, i)? {
+                ApplicationCommandInteractionDataOptionValue::User(user, ..) => {
+                    creator_id = Some(user.id)
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+    }
 
     let guild_id = command.guild_id.unwrap();
 
     // Get guild id
     let guild_db_id = database.get_guild(guild_id).await?;
 
-    let title = format!("{} publishing of events", action);
+    let title = match action {
+        Action::List => "Calendar feeds".to_string(),
+        _ => format!("{} calendar feed", action),
+    };
 
-    match action {
-        Action::Enable => {
-            let id = {
-                let row = database
-                    .client
-                    .query_opt(
-                        "
-                SELECT id FROM publishing
+    let name = match (&action, name) {
+        (Action::List, _) => {
+            let rows = database
+                .client
+                .query(
+                    "
+                SELECT id, name, channel, keyword, creator FROM publishing
                 WHERE guild = $1::BIGINT
-            ",
-                        &[&guild_db_id],
-                    )
-                    .await?;
+                ORDER BY name
+                ",
+                    &[&guild_db_id],
+                )
+                .await?;
 
-                row.map(|row| row.get::<_, String>(0))
-            };
+            if rows.is_empty() {
+                return send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    "The events of this guild are not published yet.",
+                )
+                .await;
+            }
 
-            match id {
-                Some(id) => {
-                    send_response(
-                        &ctx,
-                        &command,
-                        command_config,
-                        &title,
-                        &format!(
-                            "
-                        The calendar is already public. You can find it here:
-                        {}/{}/events.ics
-                        ",
-                            config.general.publishing_link, id
-                        ),
-                    )
-                    .await
-                }
-                None => {
-                    // Generate a random id
-                    let id: String = {
-                        const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
-                        let mut rng = rand::thread_rng();
-
-                        (0..config.general.publishing_length)
-                            .map(|_| {
-                                let idx = rng.gen_range(0..CHARSET.len());
-                                CHARSET[idx] as char
-                            })
-                            .collect()
-                    };
-
-                    database
-                        .client
-                        .execute(
-                            "
-                        INSERT INTO publishing
-                        VALUES($1::TEXT, $2::BIGINT)
-                        ",
-                            &[&id, &guild_db_id],
-                        )
-                        .await?;
-
-                    send_response(
-                        &ctx,
-                        &command,
-                        command_config,
-                        &title,
-                        &format!(
-                            "
-                        The calendar is now public and available here:
-                        {}/{}/events.ics
-                        ",
-                            config.general.publishing_link, id
+            let fields: Vec<_> = rows
+                .iter()
+                .map(|row| {
+                    let id: String = row.get(0);
+                    let channel_id = row
+                        .get::<_, Option<i64>>(2)
+                        .map(|channel| ChannelId(channel as u64));
+                    let keyword: Option<String> = row.get(3);
+                    let creator_id = row
+                        .get::<_, Option<i64>>(4)
+                        .map(|creator| UserId(creator as u64));
+
+                    (
+                        row.get::<_, String>(1),
+                        format!(
+                            "{}\n{}/{}/events.ics",
+                            describe_filters(channel_id, keyword.as_deref(), creator_id),
+                            config.general.publishing_link,
+                            id
                         ),
+                        false,
                     )
-                    .await
-                }
-            }
+                })
+                .collect();
+
+            return send_response_complex(
+                ctx,
+                command,
+                command_config,
+                &title,
+                "",
+                |embed| embed.fields(fields.clone()),
+                Vec::new(),
+            )
+            .await;
         }
-        Action::Disable => {
-            database
+        (_, Some(name)) => name,
+        (_, None) => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                "Please tell me the name of the feed.",
+            )
+            .await;
+        }
+    };
+
+    match action {
+        Action::Create => {
+            let id = generate_id(config.general.publishing_length);
+
+            let inserted = database
                 .client
                 .execute(
                     "
-            DELETE FROM publishing
-            WHERE guild = $1::BIGINT
-            ",
-                    &[&guild_db_id],
+                INSERT INTO publishing (id, guild, name, channel, keyword, creator)
+                VALUES ($1::TEXT, $2::BIGINT, $3::TEXT, $4::BIGINT, $5::TEXT, $6::BIGINT)
+                ON CONFLICT DO NOTHING
+                ",
+                    &[
+                        &id,
+                        &guild_db_id,
+                        &name,
+                        &channel_id.map(|channel_id| channel_id.0 as i64),
+                        &keyword,
+                        &creator_id.map(|creator_id| creator_id.0 as i64),
+                    ],
                 )
                 .await?;
 
-            send_response(
-                &ctx,
-                &command,
-                command_config,
-                &title,
-                "The event calendar of this guild is not public anymore.",
-            )
-            .await
+            let content = if inserted > 0 {
+                format!(
+                    "The feed \"{}\" is now public and available here:\n{}/{}/events.ics\n\n{}",
+                    name,
+                    config.general.publishing_link,
+                    id,
+                    describe_filters(channel_id, keyword.as_deref(), creator_id)
+                )
+            } else {
+                format!("There already is a feed called \"{}\".", name)
+            };
+
+            send_response(ctx, command, command_config, &title, &content).await
+        }
+        Action::Rotate => {
+            let id = generate_id(config.general.publishing_length);
+
+            let updated = database
+                .client
+                .execute(
+                    "
+                UPDATE publishing SET id = $1::TEXT
+                WHERE guild = $2::BIGINT AND name = $3::TEXT
+                ",
+                    &[&id, &guild_db_id, &name],
+                )
+                .await?;
+
+            let content = if updated > 0 {
+                format!(
+                    "The old link of the feed \"{}\" doesn't work anymore. \
+                    The feed is now available here:\n{}/{}/events.ics",
+                    name, config.general.publishing_link, id
+                )
+            } else {
+                format!("There is no feed called \"{}\".", name)
+            };
+
+            send_response(ctx, command, command_config, &title, &content).await
+        }
+        Action::Delete => {
+            let deleted = database
+                .client
+                .execute(
+                    "
+                DELETE FROM publishing
+                WHERE guild = $1::BIGINT AND name = $2::TEXT
+                ",
+                    &[&guild_db_id, &name],
+                )
+                .await?;
+
+            let content = if deleted > 0 {
+                format!("The feed \"{}\" is not public anymore.", name)
+            } else {
+                format!("There is no feed called \"{}\".", name)
+            };
+
+            send_response(ctx, command, command_config, &title, &content).await
         }
+        Action::List => unreachable!(),
+    }
+}
+
+// Generate a random id which can't be guessed
+fn generate_id(length: usize) -> String {
+    const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    let mut rng = rand::thread_rng();
+
+    (0..length)
+        .map(|_| {
+            let idx = rng.gen_range(0..CHARSET.len());
+            CHARSET[idx] as char
+        })
+        .collect()
+}
+
+// Describe which events are included in a feed
+fn describe_filters(
+    channel_id: Option<ChannelId>,
+    keyword: Option<&str>,
+    creator_id: Option<UserId>,
+) -> String {
+    let mut filters = Vec::new();
+    if let Some(channel_id) = channel_id {
+        filters.push(format!("taking place in {}", channel_id.mention()));
+    }
+    if let Some(keyword) = keyword {
+        filters.push(format!("whose name contains \"{}\"", keyword));
+    }
+    if let Some(creator_id) = creator_id {
+        filters.push(format!("created by {}", creator_id.mention()));
+    }
+
+    if filters.is_empty() {
+        "Contains all events.".to_string()
+    } else {
+        format!("Contains events {}.", filters.join(" and "))
     }
 }
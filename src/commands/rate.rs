@@ -0,0 +1,82 @@
+use serenity::{
+    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    utils::{parse_arg, send_response},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse argument
+    let rate: f64 = parse_arg(options, 0)?;
+
+    let title = "Set exchange rate";
+
+    if rate <= 0.0 {
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            title,
+            "The exchange rate has to be positive.",
+        )
+        .await;
+    }
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    // Keep the balance earned so far, the new rate only applies to upcoming upvotes
+    database
+        .client
+        .execute(
+            "
+            WITH old_rate AS (
+                SELECT COALESCE((SELECT rate FROM economy_rates WHERE guild = $1::BIGINT), 1) rate
+            ), adjusted AS (
+                INSERT INTO economy_balances (guild, \"user\", adjustment)
+                SELECT s.guild, s.\"user\",
+                FLOOR(o.rate * upvotes)::BIGINT - FLOOR($2::DOUBLE PRECISION * upvotes)::BIGINT
+                FROM scores s, old_rate o
+                WHERE s.guild = $1::BIGINT
+                ON CONFLICT (guild, \"user\")
+                DO UPDATE SET adjustment = economy_balances.adjustment + EXCLUDED.adjustment
+            )
+
+            INSERT INTO economy_rates
+            VALUES ($1::BIGINT, $2::DOUBLE PRECISION)
+            ON CONFLICT (guild)
+            DO UPDATE SET rate = $2::DOUBLE PRECISION
+            ",
+            &[&guild_db_id, &rate],
+        )
+        .await?;
+
+    send_response(
+        ctx,
+        command,
+        command_config,
+        title,
+        &format!(
+            "Each upvote a user receives from now on is worth a balance of {}.
+            The balance users have earned so far stays the same.",
+            rate
+        ),
+    )
+    .await
+}
@@ -0,0 +1,201 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
+
+use serenity::{
+    client::Context, collector::ReactionAction,
+    model::interactions::application_command::ApplicationCommandInteraction,
+};
+
+use crate::{
+    config::Command,
+    config::Config,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    strings::ERR_CMD_ARGS_INVALID,
+    utils::{parse_arg, send_response},
+};
+
+enum Mode {
+    Independent,
+    Unique,
+    Limit,
+    Verify,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Mode::Independent => "independent",
+            Mode::Unique => "unique",
+            Mode::Limit => "limit",
+            Mode::Verify => "verify",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Mode {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "independent" => Ok(Mode::Independent),
+            "unique" => Ok(Mode::Unique),
+            "limit" => Ok(Mode::Limit),
+            "verify" => Ok(Mode::Verify),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get config and database
+    let (config, database) = data!(ctx, (Config, Database));
+
+    let guild_id = command.guild_id.unwrap();
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let mode = Mode::from_str(parse_arg(options, 0)?)?;
+    let maximum = {
+        if options.len() > 1 {
+            Some(parse_arg::<i32>(options, 1)?)
+        } else {
+            None
+        }
+    };
+
+    let title = "Set reaction-role group";
+
+    if matches!(mode, Mode::Limit) && maximum.is_none() {
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            title,
+            "Please provide the maximum number of roles users can pick.",
+        )
+        .await;
+    }
+
+    send_response(
+        ctx,
+        command,
+        command_config,
+        title,
+        "React to the message of which you want to group the reaction-roles.",
+    )
+    .await?;
+
+    // Wait for the reaction
+    let reaction = guild_id
+        .await_reaction(ctx)
+        .guild_id(guild_id)
+        .author_id(command.user.id)
+        .removed(false)
+        .timeout(Duration::from_secs(config.general.interaction_timeout))
+        .await;
+
+    let reaction = match reaction.as_ref().map(|reaction| reaction.as_ref()) {
+        Some(ReactionAction::Added(reaction)) => reaction,
+        Some(ReactionAction::Removed(_)) => unreachable!(),
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                "Timed out",
+                "You took too long to respond :(",
+            )
+            .await;
+        }
+    };
+
+    // Get the guild, channel and message ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let channel_db_id = database.get_channel(guild_id, reaction.channel_id).await?;
+    let message_db_id = database
+        .get_message(guild_id, reaction.channel_id, reaction.message_id)
+        .await?;
+
+    // Remove the reaction of the user
+    reaction.delete(&ctx.http).await?;
+
+    let message = reaction.message(&ctx.http).await?;
+
+    let content = match mode {
+        Mode::Independent => {
+            database
+                .client
+                .execute(
+                    "
+                    DELETE FROM reaction_role_groups
+                    WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                    ",
+                    &[&guild_db_id, &channel_db_id, &message_db_id],
+                )
+                .await?;
+
+            format!(
+                "The reaction-roles [here]({}) can now be picked independently.",
+                message.link()
+            )
+        }
+        _ => {
+            // Only groups with a limit need a maximum
+            let maximum = if matches!(mode, Mode::Limit) {
+                maximum
+            } else {
+                None
+            };
+
+            // Insert or update the group
+            database
+                .client
+                .execute(
+                    "
+                    INSERT INTO reaction_role_groups
+                    VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::TEXT, $5::INT)
+                    ON CONFLICT (guild, channel, message)
+                    DO UPDATE SET mode = $4::TEXT, maximum = $5::INT
+                    ",
+                    &[
+                        &guild_db_id,
+                        &channel_db_id,
+                        &message_db_id,
+                        &mode.to_string(),
+                        &maximum,
+                    ],
+                )
+                .await?;
+
+            let description = match mode {
+                Mode::Unique => "Users can only hold one of them at a time.".to_string(),
+                Mode::Limit => format!(
+                    "Users can hold at most {} of them at a time.",
+                    maximum.unwrap_or_default()
+                ),
+                _ => "Users can pick them, but they won't be removed again.".to_string(),
+            };
+
+            format!(
+                "The reaction-roles [here]({}) are now grouped. {}",
+                message.link(),
+                description
+            )
+        }
+    };
+
+    send_response(ctx, command, command_config, title, &content).await
+}
@@ -0,0 +1,164 @@
+use std::convert::TryFrom;
+
+use itertools::Itertools;
+use serenity::{
+    client::Context,
+    model::{
+        channel::ReactionType, id::RoleId,
+        interactions::application_command::ApplicationCommandInteraction,
+    },
+    utils::parse_role,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    reaction_messages::{create_reaction_message, MessageRole},
+    utils::{parse_arg, send_response},
+};
+
+/// Maximum number of different reactions Discord allows on a single message.
+const MAX_REACTIONS: usize = 20;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let title: String = parse_arg(options, 0)?;
+    let description: String = parse_arg(options, 1)?;
+    let pairs: String = parse_arg(options, 2)?;
+
+    let guild_id = command.guild_id.unwrap();
+
+    let response_title = format!("Create reaction-role message {}", title);
+
+    // Parse the emoji-role pairs
+    let roles = guild_id.roles(&ctx.http).await?;
+    let tokens: Vec<_> = pairs
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .collect();
+    let pairs: Option<Vec<(ReactionType, RoleId)>> = tokens
+        .iter()
+        .tuples()
+        .map(|(&emoji, &role)| {
+            let emoji = ReactionType::try_from(emoji).ok()?;
+            let role = parse_role(role)
+                .or_else(|| role.parse().ok())
+                .map(RoleId)
+                .filter(|role| roles.contains_key(role))?;
+
+            Some((emoji, role))
+        })
+        .collect();
+
+    let pairs = match pairs {
+        Some(pairs) if !pairs.is_empty() && tokens.len() % 2 == 0 => {
+            pairs.into_iter().unique().collect::<Vec<_>>()
+        }
+        _ => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                &response_title,
+                "Please provide pairs of emojis and roles of this guild, e.g. `🎮 @Gamer 🎨 @Artist`.",
+            )
+            .await;
+        }
+    };
+
+    let emojis: Vec<_> = pairs
+        .iter()
+        .map(|(emoji, _)| emoji.clone())
+        .unique()
+        .collect();
+
+    if emojis.len() > MAX_REACTIONS {
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            &response_title,
+            &format!(
+                "A message can't have more than {} different reactions.",
+                MAX_REACTIONS
+            ),
+        )
+        .await;
+    }
+
+    // Check whether the emojis are available on the guild
+    for emoji in &emojis {
+        if let ReactionType::Custom { id, .. } = emoji {
+            if guild_id.emoji(&ctx.http, *id).await.is_err() {
+                return send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &response_title,
+                    &format!(
+                        "I couldn't find the emoji {}. Is it a valid emoji registered on this guild?",
+                        emoji
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
+    let roles: Vec<_> = pairs
+        .into_iter()
+        .map(|(emoji, role)| MessageRole {
+            emoji,
+            role,
+            slots: None,
+            hours: None,
+        })
+        .collect();
+
+    let message = match create_reaction_message(
+        ctx,
+        &database,
+        guild_id,
+        command.channel_id,
+        &title,
+        &description,
+        &roles,
+    )
+    .await?
+    {
+        Some(message) => message,
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                &response_title,
+                "I couldn't react with all of the emojis. Are they valid emojis?",
+            )
+            .await;
+        }
+    };
+
+    send_response(
+        ctx,
+        command,
+        command_config,
+        &response_title,
+        &format!(
+            "I have posted the message [here]({}). Users can pick the roles by reacting to it.",
+            message.link()
+        ),
+    )
+    .await
+}
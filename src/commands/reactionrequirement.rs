@@ -0,0 +1,173 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue::Role,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    strings::ERR_CMD_ARGS_INVALID,
+    utils::{parse_arg, parse_arg_resolved, send_response},
+};
+
+enum Action {
+    Add,
+    Remove,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Action::Add => "Add",
+            Action::Remove => "Remove",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add" => Ok(Action::Add),
+            "remove" => Ok(Action::Remove),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let action = Action::from_str(parse_arg(options, 0)?)?;
+    let role = match parse_arg_resolved(options, 1)? {
+        Role(role) => role,
+        _ => unreachable!(),
+    };
+    let requirement = match parse_arg_resolved(options, 2)? {
+        Role(role) => role,
+        _ => unreachable!(),
+    };
+    let forbidden = {
+        if options.len() > 3 {
+            parse_arg::<String>(options, 3)? == "forbid"
+        } else {
+            false
+        }
+    };
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild and role ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let role_db_id = database.get_role(guild_id, role.id).await?;
+    let requirement_db_id = database.get_role(guild_id, requirement.id).await?;
+
+    let title = format!("{} requirement for {}", action, role.name);
+
+    match action {
+        Action::Add => {
+            if role.id == requirement.id {
+                return send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    "A role can't be a requirement of itself.",
+                )
+                .await;
+            }
+
+            // Insert or update the requirement
+            database
+                .client
+                .execute(
+                    "
+            INSERT INTO reaction_role_requirements
+            VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::BOOLEAN)
+            ON CONFLICT (guild, role, requirement)
+            DO UPDATE SET forbidden = $4::BOOLEAN
+            ",
+                    &[&guild_db_id, &role_db_id, &requirement_db_id, &forbidden],
+                )
+                .await?;
+
+            let content = if forbidden {
+                format!(
+                    "Users holding the role {} can no longer pick the role {}.",
+                    requirement.mention(),
+                    role.mention()
+                )
+            } else {
+                format!(
+                    "Users need to hold the role {} to pick the role {}.",
+                    requirement.mention(),
+                    role.mention()
+                )
+            };
+
+            send_response(ctx, command, command_config, &title, &content).await
+        }
+        Action::Remove => {
+            let modified = database
+                .client
+                .execute(
+                    "
+            DELETE FROM reaction_role_requirements
+            WHERE guild = $1::BIGINT AND role = $2::BIGINT AND requirement = $3::BIGINT
+            ",
+                    &[&guild_db_id, &role_db_id, &requirement_db_id],
+                )
+                .await?;
+
+            if modified == 0 {
+                send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    &format!(
+                        "The role {} doesn't depend on the role {}. I didn't remove anything.",
+                        role.mention(),
+                        requirement.mention()
+                    ),
+                )
+                .await
+            } else {
+                send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    &format!(
+                        "The role {} no longer depends on the role {}.",
+                        role.mention(),
+                        requirement.mention()
+                    ),
+                )
+                .await
+            }
+        }
+    }
+}
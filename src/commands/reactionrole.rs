@@ -23,6 +23,9 @@ use crate::{
     database::client::Database,
     error::KowalskiError,
     error::KowalskiError::DiscordApiError,
+    pluralize,
+    reaction_messages::update_reaction_message,
+    reaction_slots::reconcile_slots,
     strings::ERR_CMD_ARGS_INVALID,
     utils::{parse_arg, parse_arg_resolved, send_response},
 };
@@ -73,13 +76,15 @@ pub async fn execute(
         Role(role) => role,
         _ => unreachable!(),
     };
-    let slots = {
-        if options.len() > 2 {
-            Some(parse_arg::<i64>(options, 2)?)
-        } else {
-            None
+    let mut slots = None;
+    let mut hours = None;
+    for i in 2..options.len() {
+        match options.get(i).unwrap().name.as_str() {
+            "slots" => slots = Some(parse_arg::<i64>(options, i)?),
+            "hours" => hours = Some(parse_arg::<i64>(options, i)?),
+            _ => unreachable!(),
         }
-    };
+    }
 
     let title = format!("{} reaction-role for {}", action, role.name);
 
@@ -138,9 +143,10 @@ pub async fn execute(
                                     "
                                 INSERT INTO reaction_roles
                                 VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::INT, $5::BIGINT,
-                                    $6::BIGINT)
+                                    $6::BIGINT, $7::BIGINT, $6::BIGINT)
                                 ON CONFLICT (guild, channel, message, emoji, role)
-                                DO UPDATE SET slots = $6::BIGINT
+                                DO UPDATE SET slots = $6::BIGINT, hours = $7::BIGINT,
+                                    capacity = $6::BIGINT
                                 ",
                                     &[
                                         &guild_db_id,
@@ -149,23 +155,43 @@ pub async fn execute(
                                         &emoji,
                                         &role_db_id,
                                         &slots,
+                                        &hours,
                                     ],
                                 )
                                 .await?;
 
+                            // Account for the members already holding the role
+                            if slots.is_some() {
+                                reconcile_slots(ctx, &database, guild_id).await?;
+                            }
+
                             // React to the message
                             let message = reaction.message(&ctx.http).await?;
                             message.react(&ctx.http, reaction.emoji.clone()).await?;
                             // Remove the reaction of the user
                             reaction.delete(&ctx.http).await?;
 
+                            // Re-render messages composed by the bot
+                            update_reaction_message(
+                                ctx,
+                                &database,
+                                guild_id,
+                                reaction.channel_id,
+                                reaction.message_id,
+                            )
+                            .await?;
+
                             let content = format!(
                                 "I will assign the role {} to users which react with {} [here]({}).
-                                There are {} role-slots available.",
+                                There are {} role-slots available.{}",
                                 role.mention(),
                                 &reaction.emoji.to_string(),
                                 &message.link(),
-                                slots.map_or("unlimited".to_string(), |num| num.to_string())
+                                slots.map_or("unlimited".to_string(), |num| num.to_string()),
+                                hours.map_or(String::new(), |hours| format!(
+                                    " The role will be removed again after {}.",
+                                    pluralize!("hour", hours)
+                                ))
                             );
 
                             send_response(ctx, command, command_config, &title, &content).await
@@ -195,6 +221,16 @@ pub async fn execute(
                                 .delete_reaction_emoji(&ctx.http, reaction.emoji.clone())
                                 .await?;
 
+                            // Re-render messages composed by the bot
+                            update_reaction_message(
+                                ctx,
+                                &database,
+                                guild_id,
+                                reaction.channel_id,
+                                reaction.message_id,
+                            )
+                            .await?;
+
                             let content = format!(
                                 "I will no longer assign the role {} to users which react with {} [here]({}).",
                                 role.mention(),
@@ -33,7 +33,11 @@ pub async fn execute(
             .client
             .query(
                 "
-                SELECT channel, message, unicode, guild_emoji, role, slots
+                SELECT channel, message, unicode, guild_emoji, role, slots, hours, (
+                    SELECT COUNT(*) FROM reaction_role_waitlist w
+                    WHERE w.guild = rr.guild AND w.channel = rr.channel
+                    AND w.message = rr.message AND w.emoji = rr.emoji AND w.role = rr.role
+                )
                 FROM reaction_roles rr
                 INNER JOIN emojis e ON emoji = id
                 WHERE rr.guild = $1::BIGINT
@@ -65,33 +69,118 @@ pub async fn execute(
             };
             let role_id = RoleId(row.get::<_, i64>(4) as u64);
             let slots: Option<i32> = row.get(5);
+            let hours: Option<i64> = row.get(6);
+            let waiting: i64 = row.get(7);
 
-            roles.push((channel_id, message_id, emoji, role_id, slots));
+            roles.push((
+                channel_id, message_id, emoji, role_id, slots, hours, waiting,
+            ));
         }
 
         roles
     };
 
+    // Get reaction-role groups
+    let groups = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT channel, message, mode, maximum
+                FROM reaction_role_groups
+                WHERE guild = $1::BIGINT
+                ORDER BY channel, message
+                ",
+                &[&guild_db_id],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                let channel_id = ChannelId(row.get::<_, i64>(0) as u64);
+                let message_id = MessageId(row.get::<_, i64>(1) as u64);
+                let mode = match row.get::<_, String>(2).as_str() {
+                    "unique" => "only one role can be held".to_string(),
+                    "limit" => format!(
+                        "at most {} can be held",
+                        pluralize!("role", row.get::<_, i32>(3))
+                    ),
+                    _ => "roles are never removed".to_string(),
+                };
+
+                format!(
+                    "[This message]({}): {}",
+                    message_id.link(channel_id, Some(guild_id)),
+                    mode
+                )
+            })
+            .join("\n")
+    };
+
+    // Get the requirements of the roles
+    let requirements = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT role, requirement, forbidden
+                FROM reaction_role_requirements
+                WHERE guild = $1::BIGINT
+                ORDER BY role, forbidden
+                ",
+                &[&guild_db_id],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                let role_id = RoleId(row.get::<_, i64>(0) as u64);
+                let requirement_id = RoleId(row.get::<_, i64>(1) as u64);
+
+                format!(
+                    "{} {} {}",
+                    role_id.mention(),
+                    if row.get::<_, bool>(2) {
+                        "can't be picked with"
+                    } else {
+                        "requires"
+                    },
+                    requirement_id.mention()
+                )
+            })
+            .join("\n")
+    };
+
     let roles = roles
         .iter()
-        .map(|(channel_id, message_id, emoji, role_id, slots)| {
-            let mut content = format!(
-                "{} when reacting with {} [here]({}).",
-                role_id.mention(),
-                emoji.to_string(),
-                message_id.link(*channel_id, Some(guild_id))
-            );
-
-            if let Some(slots) = slots {
-                content.push_str(&format!(
-                    " (There {} currently {} available)",
-                    if *slots == 1 { "is" } else { "are" },
-                    pluralize!("slot", *slots)
-                ));
-            }
-
-            content
-        })
+        .map(
+            |(channel_id, message_id, emoji, role_id, slots, hours, waiting)| {
+                let mut content = format!(
+                    "{} when reacting with {} [here]({}).",
+                    role_id.mention(),
+                    emoji.to_string(),
+                    message_id.link(*channel_id, Some(guild_id))
+                );
+
+                if let Some(slots) = slots {
+                    content.push_str(&format!(
+                        " (There {} currently {} available)",
+                        if *slots == 1 { "is" } else { "are" },
+                        pluralize!("slot", *slots)
+                    ));
+                }
+
+                if *waiting > 0 {
+                    content.push_str(&format!(" ({} waiting)", pluralize!("user", *waiting)));
+                }
+
+                if let Some(hours) = hours {
+                    content.push_str(&format!(" (Expires after {})", pluralize!("hour", *hours)));
+                }
+
+                content
+            },
+        )
         .join("\n");
 
     let title = "Reaction roles";
@@ -106,16 +195,25 @@ pub async fn execute(
         )
         .await
     } else {
-        send_response(
-            ctx,
-            command,
-            command_config,
-            title,
-            &format!(
-                "The following reaction roles are registered on this guild:\n\n{}",
-                roles
-            ),
-        )
-        .await
+        let mut content = format!(
+            "The following reaction roles are registered on this guild:\n\n{}",
+            roles
+        );
+
+        if !groups.is_empty() {
+            content.push_str(&format!(
+                "\n\nThe reaction roles of the following messages are grouped:\n\n{}",
+                groups
+            ));
+        }
+
+        if !requirements.is_empty() {
+            content.push_str(&format!(
+                "\n\nThe following roles have requirements:\n\n{}",
+                requirements
+            ));
+        }
+
+        send_response(ctx, command, command_config, title, &content).await
     }
 }
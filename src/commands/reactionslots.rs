@@ -0,0 +1,40 @@
+use serenity::{
+    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+};
+
+use crate::{
+    config::Command, data, database::client::Database, error::KowalskiError, pluralize,
+    reaction_slots::reconcile_slots, utils::send_response,
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Recount the slots from the members holding the roles
+    let corrected = reconcile_slots(ctx, &database, guild_id).await?;
+
+    let content = if corrected == 0 {
+        "The slots of all limited roles were already correct.".to_string()
+    } else {
+        format!(
+            "I have corrected the slots of {} according to the members holding them.",
+            pluralize!("role", corrected)
+        )
+    };
+
+    send_response(
+        ctx,
+        command,
+        command_config,
+        "Reconcile role slots",
+        &content,
+    )
+    .await
+}
@@ -1,4 +1,6 @@
-use chrono::{Duration, Utc};
+use std::str::FromStr;
+
+use chrono::Utc;
 use serenity::{
     client::Context, model::interactions::application_command::ApplicationCommandInteraction,
 };
@@ -10,7 +12,9 @@ use crate::{
     database::client::Database,
     error::KowalskiError,
     history::History,
-    pluralize,
+    reminders::{get_reminder_ids, reminders_enabled, Delivery, ReminderScheduler, Repetition},
+    strings::ERR_USER_REMINDERS_DISABLED,
+    time_parser::{parse_time, time_hint, DATE_FORMAT},
     utils::{parse_arg, parse_arg_name, send_response},
 };
 
@@ -19,19 +23,36 @@ pub async fn execute(
     command: &ApplicationCommandInteraction,
     command_config: &Command,
 ) -> Result<(), KowalskiError> {
-    let (config, database, history_lock) = data!(ctx, (Config, Database, History));
+    let (config, database, history_lock, scheduler) =
+        data!(ctx, (Config, Database, History, ReminderScheduler));
+
+    // Reminders are available in direct messages, but only in guilds with the utility module
+    if !reminders_enabled(&database, command.guild_id).await? {
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            "Schedule reminder",
+            ERR_USER_REMINDERS_DISABLED,
+        )
+        .await;
+    }
 
     let options = &command.data.options;
 
     // Parse arguments
     let message = parse_arg::<String>(options, 0)?;
-    let minutes = parse_arg::<i64>(options, 1)?;
-    let mut hours = 0;
-    let mut days = 0;
+    let when = parse_arg::<String>(options, 1)?;
+    let mut repeat = None;
+    let mut until = None;
+    let mut count = None;
+    let mut delivery = None;
     for i in 2..options.len() {
         match options.get(i).unwrap().name.as_str() {
-            "hours" => hours = parse_arg(options, i)?,
-            "days" => days = parse_arg(options, i)?,
+            "repeat" => repeat = Some(parse_arg::<String>(options, i)?),
+            "delivery" => delivery = Some(Delivery::from_str(parse_arg(options, i)?).unwrap()),
+            "until" => until = Some(parse_arg::<String>(options, i)?),
+            "count" => count = Some(parse_arg::<i64>(options, i)?),
             _ => unreachable!(),
         }
     }
@@ -48,7 +69,24 @@ pub async fn execute(
         );
     }
 
-    if minutes + hours + days == 0 {
+    // Get datetime of reminder in the time zone of the user
+    let time_zone = database.get_time_zone(command.user.id).await?;
+    let now = Utc::now().with_timezone(&time_zone);
+    let datetime = match parse_time(&when, now) {
+        Some(datetime) => datetime,
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                "Schedule reminder",
+                &time_hint(&when),
+            )
+            .await;
+        }
+    };
+
+    if datetime <= now {
         return send_response(
             &ctx,
             &command,
@@ -59,30 +97,36 @@ pub async fn execute(
         .await;
     }
 
-    // Get datetime of reminder
-    let datetime =
-        Utc::now() + Duration::minutes(minutes) + Duration::hours(hours) + Duration::days(days);
+    // Parse the repetition of the reminder
+    let repetition = match Repetition::parse(repeat, until, count, datetime, now) {
+        Ok(repetition) => repetition,
+        Err(why) => {
+            return send_response(ctx, command, command_config, "Schedule reminder", why).await;
+        }
+    };
 
     // Get response of the bot
     let response = command.get_interaction_response(&ctx.http).await?;
 
-    let guild_id = command.guild_id.unwrap();
-
     // Get guild, channel, message and user ids
-    let guild_db_id = database.get_guild(guild_id).await?;
-    let channel_db_id = database.get_channel(guild_id, command.channel_id).await?;
-    let message_db_id = database
-        .get_message(guild_id, command.channel_id, response.id)
-        .await?;
-    let user_db_id = database.get_user(guild_id, command.user.id).await?;
+    let (guild_db_id, channel_db_id, message_db_id, user_db_id) = get_reminder_ids(
+        &database,
+        command.guild_id,
+        command.channel_id,
+        response.id,
+        command.user.id,
+    )
+    .await?;
 
     // Add reminder to database
     database
         .client
         .execute(
             "
-    INSERT INTO reminders
-    VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::BIGINT, $5::TIMESTAMPTZ, $6::TEXT)
+    INSERT INTO reminders (guild, channel, message, \"user\", time, content, recurrence, ends,
+        remaining, delivery)
+    VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::BIGINT, $5::TIMESTAMPTZ, $6::TEXT, $7::TEXT,
+        $8::TIMESTAMPTZ, $9::INT, $10::TEXT)
     ",
             &[
                 &guild_db_id,
@@ -91,22 +135,30 @@ pub async fn execute(
                 &user_db_id,
                 &datetime,
                 &message,
+                &repetition
+                    .as_ref()
+                    .map(|repetition| repetition.recurrence.to_string()),
+                &repetition.as_ref().and_then(|repetition| repetition.ends),
+                &repetition.as_ref().and_then(Repetition::remaining),
+                &delivery.map(|delivery| delivery.to_string()),
             ],
         )
         .await?;
+    scheduler.schedule(datetime).await;
 
-    send_response(
-        &ctx,
-        &command,
-        command_config,
-        "Schedule reminder",
-        &format!(
-            "I'm going to remind you about \"{}\" in approximately {}, {} and {}!",
-            message,
-            pluralize!("day", days),
-            pluralize!("hour", hours),
-            pluralize!("minute", minutes)
-        ),
-    )
-    .await
+    let mut content = format!(
+        "I'm going to remind you about \"{}\" on {} ({}, <t:{}:R>)!",
+        message,
+        datetime.with_timezone(&time_zone).format(DATE_FORMAT),
+        time_zone.name(),
+        datetime.timestamp()
+    );
+    if let Some(repetition) = repetition {
+        content.push_str(&format!(
+            "\n\nThe reminder {}.",
+            repetition.describe(&time_zone)
+        ));
+    }
+
+    send_response(ctx, command, command_config, "Schedule reminder", &content).await
 }
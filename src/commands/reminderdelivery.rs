@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+use serenity::{
+    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    reminders::Delivery,
+    utils::{parse_arg, send_response},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse argument
+    let delivery = Delivery::from_str(parse_arg(options, 0)?).unwrap();
+
+    database
+        .client
+        .execute(
+            "
+            INSERT INTO user_settings (\"user\", delivery)
+            VALUES ($1::BIGINT, $2::TEXT)
+            ON CONFLICT (\"user\")
+            DO UPDATE SET delivery = $2::TEXT
+            ",
+            &[&(command.user.id.0 as i64), &delivery.to_string()],
+        )
+        .await?;
+
+    let content = match delivery {
+        Delivery::Channel => {
+            "From now on, I'm going to send your reminders to the channel they were set in. \
+            If I can't reach you there, I'll send you a direct message instead."
+        }
+        Delivery::Dm => "From now on, I'm going to send your reminders as direct messages.",
+        Delivery::Both => {
+            "From now on, I'm going to send your reminders to the channel they were set in \
+            and as direct messages."
+        }
+    };
+
+    send_response(ctx, command, command_config, "Reminder delivery", content).await
+}
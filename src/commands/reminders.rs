@@ -1,19 +1,22 @@
-use std::str::FromStr;
-use std::{cmp::min, time::Duration};
+use std::{cmp::min, sync::Arc, time::Duration};
 
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serenity::{
     builder::CreateActionRow,
     client::Context,
     model::{
         channel::ReactionType,
-        id::{ChannelId, UserId},
+        id::{ChannelId, GuildId, UserId},
         interactions::{
             application_command::{
                 ApplicationCommandInteraction,
                 ApplicationCommandInteractionDataOptionValue as DataOptionValue,
             },
-            message_component::ButtonStyle,
+            message_component::{
+                ActionRowComponent, ButtonStyle, InputTextStyle, MessageComponentInteraction,
+            },
+            InteractionResponseType,
         },
         user::User,
     },
@@ -27,28 +30,63 @@ use crate::{
     database::client::Database,
     error::KowalskiError,
     error::KowalskiError::DiscordApiError,
+    reminders::{describe_repetition, ReminderScheduler, EDIT_REMINDER_ID},
     row_id,
     strings::ERR_CMD_ARGS_INVALID,
-    utils::{parse_arg_resolved, send_response, send_response_complex},
+    time_parser::{parse_time, time_hint, DATE_FORMAT},
+    utils::{create_embed, parse_arg_resolved, send_response, send_response_complex},
 };
 
+/// Format used to prefill the time when editing a reminder.
+const EDIT_FORMAT: &str = "%Y-%m-%d %H:%M";
+
 enum ComponentInteractionResponse {
     Left,
     Right,
+    Cancel(usize),
+    Edit(usize, Arc<MessageComponentInteraction>),
 }
 
-impl FromStr for ComponentInteractionResponse {
-    type Err = KowalskiError;
+impl ComponentInteractionResponse {
+    fn from_interaction(
+        interaction: Arc<MessageComponentInteraction>,
+    ) -> Result<Self, KowalskiError> {
+        // Get the index of the reminder picked in a select menu
+        let selected = interaction
+            .data
+            .values
+            .first()
+            .and_then(|value| value.parse().ok());
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "left" => Ok(ComponentInteractionResponse::Left),
-            "right" => Ok(ComponentInteractionResponse::Right),
+        match (interaction.data.custom_id.as_str(), selected) {
+            ("left", _) => Ok(ComponentInteractionResponse::Left),
+            ("right", _) => Ok(ComponentInteractionResponse::Right),
+            ("cancel", Some(index)) => Ok(ComponentInteractionResponse::Cancel(index)),
+            (EDIT_REMINDER_ID, Some(index)) => {
+                Ok(ComponentInteractionResponse::Edit(index, interaction))
+            }
             _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
         }
     }
 }
 
+struct Page {
+    index: usize,
+    count: usize,
+    size: usize,
+    timeout: Duration,
+}
+
+struct Reminder {
+    id: i32,
+    channel_id: ChannelId,
+    user_id: UserId,
+    time: DateTime<Utc>,
+    content: String,
+    repetition: Option<String>,
+    announcement: bool,
+}
+
 pub async fn execute(
     ctx: &Context,
     command: &ApplicationCommandInteraction,
@@ -59,8 +97,8 @@ pub async fn execute(
 
     let guild_id = command.guild_id.unwrap();
 
-    // Get guild id
-    let guild_db_id = database.get_guild(guild_id).await?;
+    // Show the reminders in the time zone of the user
+    let time_zone = database.get_time_zone(command.user.id).await?;
 
     let options = &command.data.options;
 
@@ -75,112 +113,145 @@ pub async fn execute(
         None
     };
 
+    let mut reminders = get_reminders(&database, guild_id, user, &time_zone).await?;
+    let mut page_index = 0;
+    let page_size = config.general.reminder_list_size;
+    let timeout = Duration::from_secs(config.general.interaction_timeout);
+
+    // Loop through interactions until there is a timeout
+    loop {
+        if reminders.is_empty() {
+            let title = match user {
+                Some(user) => format!("Reminders of {}", user.name),
+                None => "Reminders".to_string(),
+            };
+
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                "Looks like there are no reminders to display :(",
+            )
+            .await;
+        }
+
+        let page_count = reminders.len().div_ceil(page_size);
+        page_index = min(page_index, page_count - 1);
+
+        let page = Page {
+            index: page_index,
+            count: page_count,
+            size: page_size,
+            timeout,
+        };
+        let interaction = show_page(
+            ctx,
+            command,
+            command_config,
+            user,
+            &reminders,
+            &time_zone,
+            page,
+        )
+        .await?;
+
+        match interaction {
+            Some(ComponentInteractionResponse::Left) => page_index -= 1,
+            Some(ComponentInteractionResponse::Right) => page_index += 1,
+            Some(ComponentInteractionResponse::Cancel(index)) => {
+                if let Some(reminder) = reminders.get(index) {
+                    cancel_reminder(&database, reminder).await?;
+                }
+
+                reminders = get_reminders(&database, guild_id, user, &time_zone).await?;
+            }
+            Some(ComponentInteractionResponse::Edit(index, interaction)) => {
+                if let Some(reminder) = reminders.get(index) {
+                    edit_reminder(
+                        ctx,
+                        &database,
+                        command.user.id,
+                        reminder,
+                        &interaction,
+                        &time_zone,
+                        timeout,
+                    )
+                    .await?;
+                }
+
+                reminders = get_reminders(&database, guild_id, user, &time_zone).await?;
+            }
+            None => break,
+        }
+    }
+
+    // Remove components
+    command
+        .edit_original_interaction_response(&ctx.http, |response| {
+            response.components(|components| components)
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn get_reminders(
+    database: &Database,
+    guild_id: GuildId,
+    user: Option<&User>,
+    time_zone: &Tz,
+) -> Result<Vec<Reminder>, KowalskiError> {
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
     // Get reminders depending on the given argument
-    let reminders: Vec<_> = match user {
+    let rows = match user {
         Some(user) => {
             // Get user id
             let user_db_id = database.get_user(guild_id, user.id).await?;
 
-            let rows = database
+            database
                 .client
                 .query(
                     "
-            SELECT channel, time, content
+            SELECT channel, \"user\", time, content, recurrence, ends, remaining, announcement, id
             FROM reminders
             WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
             ORDER BY time
             ",
                     &[&guild_db_id, &user_db_id],
                 )
-                .await?;
-
-            rows.iter()
-                .map(|row| {
-                    (
-                        row_id!(ChannelId, row, 0),
-                        None,
-                        row.get::<_, DateTime<Utc>>(1),
-                        row.get(2),
-                    )
-                })
-                .collect()
+                .await?
         }
         None => {
-            // Query the next reminder_list_size reminders
-            let rows = database
+            database
                 .client
                 .query(
                     "
-            SELECT channel, \"user\", time, content
+            SELECT channel, \"user\", time, content, recurrence, ends, remaining, announcement, id
             FROM reminders
             WHERE guild = $1::BIGINT
             ORDER BY time
             ",
                     &[&guild_db_id],
                 )
-                .await?;
-
-            rows.iter()
-                .map(|row| {
-                    (
-                        row_id!(ChannelId, row, 0),
-                        Some(row_id!(UserId, row, 1)),
-                        row.get::<_, DateTime<Utc>>(2),
-                        row.get(3),
-                    )
-                })
-                .collect()
+                .await?
         }
     };
 
-    if reminders.is_empty() {
-        let title = match user {
-            Some(user) => format!("Reminders of {}", user.name),
-            None => "Reminders".to_string(),
-        };
-
-        send_response(
-            ctx,
-            command,
-            command_config,
-            &title,
-            "Looks like there are no reminders to display :(",
-        )
-        .await
-    } else {
-        let mut page_index = 0;
-        let page_size = config.general.reminder_list_size;
-        let page_count = (reminders.len() + page_size - 1) / page_size;
-
-        // Loop through interactions until there is a timeout
-        while let Some(interaction) = show_page(
-            ctx,
-            command,
-            command_config,
-            user,
-            &reminders,
-            page_index,
-            page_count,
-            page_size,
-            Duration::from_secs(config.general.interaction_timeout),
-        )
-        .await?
-        {
-            match interaction {
-                ComponentInteractionResponse::Left => page_index -= 1,
-                ComponentInteractionResponse::Right => page_index += 1,
-            }
-        }
-
-        // Remove components
-        command
-            .edit_original_interaction_response(&ctx.http, |response| {
-                response.components(|components| components)
-            })
-            .await?;
-
-        Ok(())
-    }
+    Ok(rows
+        .iter()
+        .map(|row| Reminder {
+            id: row.get(8),
+            channel_id: row_id!(ChannelId, row, 0),
+            user_id: row_id!(UserId, row, 1),
+            time: row.get(2),
+            content: row.get(3),
+            repetition: describe_repetition(row, 4, time_zone),
+            announcement: row.get(7),
+        })
+        .collect())
 }
 
 async fn show_page(
@@ -188,15 +259,28 @@ async fn show_page(
     command: &ApplicationCommandInteraction,
     command_config: &Command,
     user: Option<&User>,
-    reminders: &Vec<(ChannelId, Option<UserId>, DateTime<Utc>, String)>,
-    index: usize,
-    count: usize,
-    size: usize,
-    timeout: Duration,
+    reminders: &[Reminder],
+    time_zone: &Tz,
+    Page {
+        index,
+        count,
+        size,
+        timeout,
+    }: Page,
 ) -> Result<Option<ComponentInteractionResponse>, KowalskiError> {
     // Get config
     let config = data!(ctx, Config);
 
+    // Get start index
+    let start = index * size;
+    // Get page slice
+    let page = {
+        let end = min(start + size, reminders.len());
+        &reminders[start..end]
+    };
+
+    let mut rows = Vec::new();
+
     let mut row = CreateActionRow::default();
     row.create_button(|button| {
         button
@@ -212,6 +296,43 @@ async fn show_page(
             .style(ButtonStyle::Secondary)
             .disabled(index >= count - 1)
     });
+    rows.push(row);
+
+    // Only offer to cancel or edit the reminders of the user
+    let own: Vec<_> = page
+        .iter()
+        .enumerate()
+        .filter(|(_, reminder)| reminder.user_id == command.user.id)
+        .map(|(i, reminder)| {
+            let label = format!(
+                "{}: {}",
+                reminder.time.with_timezone(time_zone).format(EDIT_FORMAT),
+                reminder.content
+            );
+
+            (start + i, label.chars().take(100).collect::<String>())
+        })
+        .collect();
+
+    if !own.is_empty() {
+        for (custom_id, placeholder) in [
+            ("cancel", "Cancel a reminder"),
+            (EDIT_REMINDER_ID, "Edit a reminder"),
+        ] {
+            let mut row = CreateActionRow::default();
+            row.create_select_menu(|menu| {
+                menu.custom_id(custom_id)
+                    .placeholder(placeholder)
+                    .options(|options| {
+                        for (index, label) in &own {
+                            options.create_option(|option| option.label(label).value(index));
+                        }
+                        options
+                    })
+            });
+            rows.push(row);
+        }
+    }
 
     let title = match user {
         Some(user) => format!("Reminders of {} (Page {}/{})", user.name, index + 1, count),
@@ -226,38 +347,53 @@ async fn show_page(
         &title,
         "",
         |embed| {
-            // Get start index
-            let start = index * size;
-            // Get page slice
-            let page = {
-                let end = min(start + size, reminders.len());
-                &reminders[start..end]
-            };
-
-            embed.fields(page.iter().map(|(channel_id, user_id, datetime, content)| {
+            embed.fields(page.iter().map(|reminder| {
                 // Cut of content after a certain length
-                let content = &content[..min(
-                    config.general.reminder_list_max_message_length,
-                    content.len(),
-                )];
+                let content: String = reminder
+                    .content
+                    .chars()
+                    .take(config.general.reminder_list_max_message_length)
+                    .collect();
+
+                let kind = if reminder.announcement {
+                    "Announcement"
+                } else {
+                    "Reminder"
+                };
+                let mut value = if user.is_none() {
+                    format!(
+                        "`#{}` {} of {} in {}: {}",
+                        reminder.id,
+                        kind,
+                        reminder.user_id.mention(),
+                        reminder.channel_id.mention(),
+                        content
+                    )
+                } else {
+                    format!(
+                        "`#{}` {} in {}: {}",
+                        reminder.id,
+                        kind,
+                        reminder.channel_id.mention(),
+                        content
+                    )
+                };
+                if let Some(repetition) = &reminder.repetition {
+                    value.push_str(&format!("\n*{}*", repetition));
+                }
 
                 (
-                    format!("<t:{}:f>", datetime.timestamp()),
-                    if let Some(user_id) = user_id {
-                        format!(
-                            "Reminder of {} in {}: {}",
-                            user_id.clone().mention().to_string(),
-                            channel_id.clone().mention(),
-                            content
-                        )
-                    } else {
-                        format!("Reminder in {}: {}", channel_id.clone().mention(), content)
-                    },
+                    format!(
+                        "{} ({})",
+                        reminder.time.with_timezone(time_zone).format(DATE_FORMAT),
+                        time_zone.name()
+                    ),
+                    value,
                     false,
                 )
             }))
         },
-        vec![row],
+        rows,
     )
     .await?;
 
@@ -265,16 +401,151 @@ async fn show_page(
     let message = command.get_interaction_response(&ctx.http).await?;
     // Get the interaction response
     let interaction = message
-        .await_component_interaction(&ctx)
+        .await_component_interaction(ctx)
         .author_id(command.user.id.0)
         .timeout(timeout)
         .await;
     let response = match interaction {
-        Some(interaction) => Some(ComponentInteractionResponse::from_str(
-            interaction.data.custom_id.as_str(),
-        )?),
+        Some(interaction) => Some(ComponentInteractionResponse::from_interaction(interaction)?),
         None => None,
     };
 
     Ok(response)
 }
+
+async fn cancel_reminder(database: &Database, reminder: &Reminder) -> Result<(), KowalskiError> {
+    database
+        .client
+        .execute("DELETE FROM reminders WHERE id = $1::INT", &[&reminder.id])
+        .await?;
+
+    Ok(())
+}
+
+async fn edit_reminder(
+    ctx: &Context,
+    database: &Database,
+    user_id: UserId,
+    reminder: &Reminder,
+    interaction: &MessageComponentInteraction,
+    time_zone: &Tz,
+    timeout: Duration,
+) -> Result<(), KowalskiError> {
+    // Ask for the new content and time
+    interaction
+        .create_interaction_response(&ctx.http, |response| {
+            response
+                .kind(InteractionResponseType::Modal)
+                .interaction_response_data(|data| {
+                    data.custom_id(EDIT_REMINDER_ID)
+                        .title("Edit reminder")
+                        .components(|components| {
+                            components
+                                .create_action_row(|row| {
+                                    row.create_input_text(|input| {
+                                        input
+                                            .custom_id("content")
+                                            .label("Message")
+                                            .style(InputTextStyle::Paragraph)
+                                            .value(&reminder.content)
+                                            .required(true)
+                                    })
+                                })
+                                .create_action_row(|row| {
+                                    row.create_input_text(|input| {
+                                        input
+                                            .custom_id("when")
+                                            .label("When")
+                                            .style(InputTextStyle::Short)
+                                            .value(
+                                                reminder
+                                                    .time
+                                                    .with_timezone(time_zone)
+                                                    .format(EDIT_FORMAT),
+                                            )
+                                            .required(true)
+                                    })
+                                })
+                        })
+                })
+        })
+        .await?;
+
+    let submit = match interaction
+        .message
+        .await_modal_interaction(ctx)
+        .author_id(user_id.0)
+        .timeout(timeout)
+        .await
+    {
+        Some(submit) => submit,
+        None => return Ok(()),
+    };
+
+    // Get the values of the input fields
+    let mut content = reminder.content.clone();
+    let mut time = reminder.time;
+    for component in submit
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+    {
+        if let ActionRowComponent::InputText(input) = component {
+            match input.custom_id.as_str() {
+                "content" => content = input.value.clone(),
+                "when" => {
+                    // Keep the exact time if it hasn't been touched
+                    let current = reminder.time.with_timezone(time_zone).format(EDIT_FORMAT);
+                    if input.value.trim() == current.to_string() {
+                        continue;
+                    }
+
+                    let now = Utc::now().with_timezone(time_zone);
+                    match parse_time(&input.value, now).filter(|time| *time > now) {
+                        Some(parsed) => time = parsed,
+                        None => {
+                            let embed = create_embed("Edit reminder", &time_hint(&input.value));
+
+                            submit
+                                .create_interaction_response(&ctx.http, |response| {
+                                    response
+                                        .kind(InteractionResponseType::ChannelMessageWithSource)
+                                        .interaction_response_data(|data| {
+                                            data.ephemeral(true).add_embed(embed)
+                                        })
+                                })
+                                .await?;
+
+                            return Ok(());
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    submit
+        .create_interaction_response(&ctx.http, |response| {
+            response.kind(InteractionResponseType::DeferredUpdateMessage)
+        })
+        .await?;
+
+    database
+        .client
+        .execute(
+            "
+            UPDATE reminders SET time = $2::TIMESTAMPTZ, content = $3::TEXT, retry = NULL
+            WHERE id = $1::INT
+            ",
+            &[&reminder.id, &time, &content],
+        )
+        .await?;
+
+    // Wake the scheduler if the reminder was moved to an earlier point in time
+    let scheduler = data!(ctx, ReminderScheduler);
+    scheduler.schedule(time).await;
+
+    Ok(())
+}
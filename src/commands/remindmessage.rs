@@ -0,0 +1,269 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use serenity::{
+    builder::CreateActionRow,
+    client::Context,
+    model::interactions::{
+        application_command::{ApplicationCommandInteraction, ResolvedTarget},
+        message_component::{ActionRowComponent, InputTextStyle, MessageComponentInteraction},
+        InteractionResponseType,
+    },
+};
+
+use crate::{
+    config::Command,
+    config::Config,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    reminders::{get_reminder_ids, reminders_enabled, ReminderScheduler, PICK_TIME_ID},
+    strings::{ERR_CMD_ARGS_INVALID, ERR_USER_REMINDERS_DISABLED},
+    time_parser::{parse_time, time_hint, DATE_FORMAT},
+    utils::{send_response, send_response_complex},
+};
+
+/// Points in time offered when picking the time of the reminder.
+const PRESETS: [(&str, &str); 5] = [
+    ("In 10 minutes", "in 10m"),
+    ("In 1 hour", "in 1h"),
+    ("In 3 hours", "in 3h"),
+    ("Tomorrow morning", "tomorrow 9am"),
+    ("Next week", "in 1w"),
+];
+
+/// Maximum number of characters of the message quoted in the reminder.
+const QUOTE_LENGTH: usize = 200;
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    let (config, database, scheduler) = data!(ctx, (Config, Database, ReminderScheduler));
+
+    // Reminders are available in direct messages, but only in guilds with the utility module
+    if !reminders_enabled(&database, command.guild_id).await? {
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            "Remind me about this",
+            ERR_USER_REMINDERS_DISABLED,
+        )
+        .await;
+    }
+
+    // Get the message the command was used on
+    let target = match command.data.target() {
+        Some(ResolvedTarget::Message(message)) => message,
+        _ => return Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+    };
+
+    // Ask for the point in time of the reminder
+    let mut row = CreateActionRow::default();
+    row.create_select_menu(|menu| {
+        menu.custom_id(PICK_TIME_ID)
+            .placeholder("Pick a time")
+            .options(|options| {
+                for (label, value) in PRESETS {
+                    options.create_option(|option| option.label(label).value(value));
+                }
+                options.create_option(|option| option.label("Another time...").value("custom"))
+            })
+    });
+
+    send_response_complex(
+        ctx,
+        command,
+        command_config,
+        "Remind me about this",
+        &format!(
+            "When should I remind you about [this message]({})?",
+            target.link()
+        ),
+        |embed| embed,
+        vec![row],
+    )
+    .await?;
+
+    let timeout = Duration::from_secs(config.general.interaction_timeout);
+
+    // Get the message
+    let message = command.get_interaction_response(&ctx.http).await?;
+    // Get the interaction response
+    let interaction = message
+        .await_component_interaction(ctx)
+        .author_id(command.user.id.0)
+        .timeout(timeout)
+        .await;
+
+    let when = match interaction {
+        Some(interaction) => match interaction.data.values.first().map(String::as_str) {
+            Some("custom") => ask_time(ctx, &interaction, timeout).await?,
+            Some(value) => {
+                interaction
+                    .create_interaction_response(&ctx.http, |response| {
+                        response.kind(InteractionResponseType::DeferredUpdateMessage)
+                    })
+                    .await?;
+
+                Some(value.to_string())
+            }
+            None => return Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        },
+        None => None,
+    };
+
+    let when = match when {
+        Some(when) => when,
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                "Timed out",
+                "You took too long to respond :(",
+            )
+            .await;
+        }
+    };
+
+    // Get datetime of reminder in the time zone of the user
+    let time_zone = database.get_time_zone(command.user.id).await?;
+    let now = Utc::now().with_timezone(&time_zone);
+    let datetime = match parse_time(&when, now).filter(|datetime| *datetime > now) {
+        Some(datetime) => datetime,
+        None => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                "Remind me about this",
+                &time_hint(&when),
+            )
+            .await;
+        }
+    };
+
+    // Link to the message and quote its beginning
+    let mut content = format!("[Jump to the message]({})", target.link());
+    let quote: String = target.content.chars().take(QUOTE_LENGTH).collect();
+    if !quote.is_empty() {
+        content.push_str("\n\n");
+        for line in quote.lines() {
+            content.push_str(&format!("> {}\n", line));
+        }
+        if quote.len() < target.content.len() {
+            content.push_str("> ...");
+        }
+    }
+
+    // Get guild, channel, message and user ids, delivered reminders reply to the message itself
+    let (guild_db_id, channel_db_id, message_db_id, user_db_id) = get_reminder_ids(
+        &database,
+        command.guild_id,
+        target.channel_id,
+        target.id,
+        command.user.id,
+    )
+    .await?;
+
+    // Add reminder to database
+    database
+        .client
+        .execute(
+            "
+    INSERT INTO reminders (guild, channel, message, \"user\", time, content)
+    VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::BIGINT, $5::TIMESTAMPTZ, $6::TEXT)
+    ",
+            &[
+                &guild_db_id,
+                &channel_db_id,
+                &message_db_id,
+                &user_db_id,
+                &datetime,
+                &content.trim_end(),
+            ],
+        )
+        .await?;
+    scheduler.schedule(datetime).await;
+
+    send_response(
+        ctx,
+        command,
+        command_config,
+        "Remind me about this",
+        &format!(
+            "I'm going to remind you about [this message]({}) on {} ({}, <t:{}:R>)!",
+            target.link(),
+            datetime.with_timezone(&time_zone).format(DATE_FORMAT),
+            time_zone.name(),
+            datetime.timestamp()
+        ),
+    )
+    .await
+}
+
+// Ask for a custom point in time using a modal
+async fn ask_time(
+    ctx: &Context,
+    interaction: &MessageComponentInteraction,
+    timeout: Duration,
+) -> Result<Option<String>, KowalskiError> {
+    interaction
+        .create_interaction_response(&ctx.http, |response| {
+            response
+                .kind(InteractionResponseType::Modal)
+                .interaction_response_data(|data| {
+                    data.custom_id(PICK_TIME_ID)
+                        .title("Remind me about this")
+                        .components(|components| {
+                            components.create_action_row(|row| {
+                                row.create_input_text(|input| {
+                                    input
+                                        .custom_id("when")
+                                        .label("When")
+                                        .style(InputTextStyle::Short)
+                                        .placeholder("e.g. tomorrow 9am")
+                                        .required(true)
+                                })
+                            })
+                        })
+                })
+        })
+        .await?;
+
+    let submit = match interaction
+        .message
+        .await_modal_interaction(ctx)
+        .author_id(interaction.user.id.0)
+        .timeout(timeout)
+        .await
+    {
+        Some(submit) => submit,
+        None => return Ok(None),
+    };
+
+    submit
+        .create_interaction_response(&ctx.http, |response| {
+            response.kind(InteractionResponseType::DeferredUpdateMessage)
+        })
+        .await?;
+
+    // Get the value of the input field
+    let when = submit
+        .data
+        .components
+        .iter()
+        .flat_map(|row| row.components.iter())
+        .find_map(|component| match component {
+            ActionRowComponent::InputText(input) if input.custom_id == "when" => {
+                Some(input.value.clone())
+            }
+            _ => None,
+        });
+
+    Ok(when)
+}
@@ -0,0 +1,258 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue::Role,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    reaction_slots::reconcile_slots,
+    role_menus::{update_menu, ROLE_MENU_SIZE},
+    strings::ERR_CMD_ARGS_INVALID,
+    utils::{create_embed, parse_arg, parse_arg_resolved, send_response},
+};
+
+enum Action {
+    Add,
+    Remove,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Action::Add => "Add",
+            Action::Remove => "Remove",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add" => Ok(Action::Add),
+            "remove" => Ok(Action::Remove),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let action = Action::from_str(parse_arg(options, 0)?)?;
+    let name: String = parse_arg(options, 1)?;
+    let role = match parse_arg_resolved(options, 2)? {
+        Role(role) => role,
+        _ => unreachable!(),
+    };
+    let mut slots = None;
+    let mut kind = None;
+    for i in 3..options.len() {
+        match options.get(i).unwrap().name.as_str() {
+            "slots" => slots = Some(parse_arg::<i32>(options, i)?),
+            "kind" => kind = Some(parse_arg::<String>(options, i)?),
+            _ => unreachable!(),
+        }
+    }
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild and role ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let role_db_id = database.get_role(guild_id, role.id).await?;
+
+    let title = format!("{} role {} in menu {}", action, role.name, name);
+
+    // Get the kind of the menu if it already exists
+    let current = database
+        .client
+        .query_opt(
+            "
+            SELECT kind FROM role_menus
+            WHERE guild = $1::BIGINT AND name = $2::TEXT
+            ",
+            &[&guild_db_id, &name],
+        )
+        .await?
+        .map(|row| row.get::<_, String>(0));
+
+    match action {
+        Action::Add => {
+            match current {
+                Some(current) => {
+                    let count: i64 = database
+                        .client
+                        .query_one(
+                            "
+                    SELECT COUNT(*) FROM role_menu_roles
+                    WHERE guild = $1::BIGINT AND name = $2::TEXT AND role != $3::BIGINT
+                    ",
+                            &[&guild_db_id, &name, &role_db_id],
+                        )
+                        .await?
+                        .get(0);
+
+                    if count as usize >= ROLE_MENU_SIZE {
+                        return send_response(
+                            ctx,
+                            command,
+                            command_config,
+                            &title,
+                            &format!("A role menu can't hold more than {} roles.", ROLE_MENU_SIZE),
+                        )
+                        .await;
+                    }
+
+                    // Switch the kind of the menu if requested
+                    let kind = kind.unwrap_or(current);
+
+                    database
+                        .client
+                        .execute(
+                            "
+                    UPDATE role_menus
+                    SET kind = $3::TEXT
+                    WHERE guild = $1::BIGINT AND name = $2::TEXT
+                    ",
+                            &[&guild_db_id, &name, &kind],
+                        )
+                        .await?;
+                }
+                None => {
+                    // Post the message of the new menu in the current channel
+                    let embed = create_embed(&name, "This role menu is being set up...");
+                    let message = command
+                        .channel_id
+                        .send_message(&ctx.http, |message| message.set_embeds(vec![embed]))
+                        .await?;
+
+                    // Get channel and message ids
+                    let channel_db_id = database.get_channel(guild_id, command.channel_id).await?;
+                    let message_db_id = database
+                        .get_message(guild_id, command.channel_id, message.id)
+                        .await?;
+
+                    database
+                        .client
+                        .execute(
+                            "
+                    INSERT INTO role_menus
+                    VALUES ($1::BIGINT, $2::TEXT, $3::BIGINT, $4::BIGINT, $5::TEXT)
+                    ",
+                            &[
+                                &guild_db_id,
+                                &name,
+                                &channel_db_id,
+                                &message_db_id,
+                                &kind.unwrap_or_else(|| "buttons".to_string()),
+                            ],
+                        )
+                        .await?;
+                }
+            }
+
+            // Insert or update the role
+            database
+                .client
+                .execute(
+                    "
+            INSERT INTO role_menu_roles
+            VALUES ($1::BIGINT, $2::TEXT, $3::BIGINT, $4::INT, $4::INT)
+            ON CONFLICT (guild, name, role)
+            DO UPDATE SET slots = $4::INT, capacity = $4::INT
+            ",
+                    &[&guild_db_id, &name, &role_db_id, &slots],
+                )
+                .await?;
+
+            // Account for the members already holding the role
+            if slots.is_some() {
+                reconcile_slots(ctx, &database, guild_id).await?;
+            }
+
+            update_menu(ctx, &database, guild_id, &name).await?;
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                &format!(
+                    "Users can now pick the role {} in the role menu **{}**.
+                    There are {} role-slots available.",
+                    role.mention(),
+                    name,
+                    slots.map_or("unlimited".to_string(), |num| num.to_string())
+                ),
+            )
+            .await
+        }
+        Action::Remove => {
+            let modified = database
+                .client
+                .execute(
+                    "
+            DELETE FROM role_menu_roles
+            WHERE guild = $1::BIGINT AND name = $2::TEXT AND role = $3::BIGINT
+            ",
+                    &[&guild_db_id, &name, &role_db_id],
+                )
+                .await?;
+
+            if modified == 0 {
+                send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    &format!(
+                        "The role {} is not part of the role menu **{}**. \
+                        I didn't remove anything.",
+                        role.mention(),
+                        name
+                    ),
+                )
+                .await
+            } else {
+                // Update the menu or delete it if it is empty
+                update_menu(ctx, &database, guild_id, &name).await?;
+
+                send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    &format!(
+                        "Users can no longer pick the role {} in the role menu **{}**.",
+                        role.mention(),
+                        name
+                    ),
+                )
+                .await
+            }
+        }
+    }
+}
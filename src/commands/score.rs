@@ -16,6 +16,7 @@ use crate::{
     data,
     database::client::Database,
     error::KowalskiError,
+    pluralize,
     utils::{parse_arg_resolved, send_response_complex},
 };
 
@@ -46,28 +47,62 @@ pub async fn execute(
     let guild_db_id = database.get_guild(guild_id).await?;
     let user_db_id = database.get_user(guild_id, user.id).await?;
 
-    // Analyze reactions of the user
-    let (upvotes, downvotes) = {
+    // Analyze reactions, voice and message activity of the user
+    let (upvotes, downvotes, voice, messages, score) = {
         let row = database
             .client
-            .query_one(
+            .query_opt(
                 "
-        SELECT SUM(CASE WHEN upvote THEN 1 END) upvotes,
-        SUM(CASE WHEN NOT upvote THEN 1 END) downvotes
-        FROM score_reactions r
-        INNER JOIN score_emojis se ON r.guild = se.guild AND r.emoji = se.emoji
-        WHERE r.guild = $1::BIGINT AND user_to = $2::BIGINT
+        SELECT upvotes, downvotes, voice, messages, score
+        FROM scores
+        WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
         ",
                 &[&guild_db_id, &user_db_id],
             )
             .await?;
 
-        let upvotes: Option<i64> = row.get(0);
-        let downvotes: Option<i64> = row.get(1);
+        row.map_or((0, 0, 0, 0, 0), |row| {
+            (
+                row.get::<_, i64>(0),
+                row.get::<_, i64>(1),
+                row.get::<_, i64>(2),
+                row.get::<_, i64>(3),
+                row.get::<_, i64>(4),
+            )
+        })
+    };
+    let minutes = {
+        let row = database
+            .client
+            .query_opt(
+                "
+        SELECT minutes FROM voice_activity
+        WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
+        ",
+                &[&guild_db_id, &user_db_id],
+            )
+            .await?;
+
+        row.map_or(0, |row| row.get::<_, i64>(0))
+    };
+    let achievements = {
+        let rows = database
+            .client
+            .query(
+                "
+        SELECT name FROM score_achievement_users au
+        INNER JOIN score_achievements a ON au.achievement = a.id
+        WHERE au.guild = $1::BIGINT AND au.\"user\" = $2::BIGINT
+        ORDER BY time
+        ",
+                &[&guild_db_id, &user_db_id],
+            )
+            .await?;
 
-        (upvotes.unwrap_or_default(), downvotes.unwrap_or_default())
+        rows.iter()
+            .map(|row| row.get::<_, String>(0))
+            .collect::<Vec<_>>()
     };
-    let score = upvotes - downvotes;
     let emojis = {
         let rows = database
             .client
@@ -110,21 +145,22 @@ pub async fn execute(
         emojis
     };
     let rank = {
-        let row = database.client.query_opt("
+        let row = database
+            .client
+            .query_opt(
+                "
             WITH ranks AS (
-                SELECT user_to,
-                RANK() OVER (
-                    ORDER BY COUNT(*) FILTER (WHERE upvote) - COUNT(*) FILTER (WHERE NOT upvote) DESC, user_to
-                ) rank
-                FROM score_reactions r
-                INNER JOIN score_emojis se ON r.guild = se.guild AND r.emoji = se.emoji
-                WHERE r.guild = $1::BIGINT
-                GROUP BY user_to
+                SELECT \"user\", RANK() OVER (ORDER BY score DESC, \"user\") rank
+                FROM scores
+                WHERE guild = $1::BIGINT
             )
 
             SELECT rank FROM ranks
-            WHERE user_to = $2::BIGINT
-            ", &[&guild_db_id, &user_db_id]).await?;
+            WHERE \"user\" = $2::BIGINT
+            ",
+                &[&guild_db_id, &user_db_id],
+            )
+            .await?;
 
         row.map(|row| row.get::<_, i64>(0))
     };
@@ -264,7 +300,34 @@ pub async fn execute(
                 bottom_users = "Not available".to_string();
             }
 
+            let voice = if minutes > 0 {
+                format!(
+                    "**{}** from {} spent talking in voice channels",
+                    voice,
+                    pluralize!("minute", minutes)
+                )
+            } else {
+                "Not available".to_string()
+            };
+
+            let messages = if messages > 0 {
+                format!("**{}** awarded messages", messages)
+            } else {
+                "Not available".to_string()
+            };
+
+            let mut achievements = achievements
+                .iter()
+                .map(|name| format!("**{}**", name))
+                .join(", ");
+            if achievements.is_empty() {
+                achievements = "Not available".to_string();
+            }
+
             embed.fields(vec![
+                ("Achievements", achievements, false),
+                ("Voice activity", voice, false),
+                ("Message activity", messages, false),
                 ("Emojis", emojis, false),
                 ("Top 5 benefactors", top_users, false),
                 ("Top 5 haters", bottom_users, false),
@@ -58,13 +58,10 @@ pub async fn execute(
             .client
             .query(
                 "
-        SELECT user_to, COUNT(*) FILTER (WHERE upvote) upvotes,
-        COUNT(*) FILTER (WHERE NOT upvote) downvotes
-        FROM score_reactions r
-        INNER JOIN score_emojis se ON r.guild = se.guild AND r.emoji = se.emoji
-        WHERE r.guild = $1::BIGINT
-        GROUP BY user_to
-        ORDER BY COUNT(*) FILTER (WHERE upvote) - COUNT(*) FILTER (WHERE NOT upvote) DESC, user_to
+        SELECT \"user\", upvotes, downvotes, voice, messages, score
+        FROM scores
+        WHERE guild = $1::BIGINT
+        ORDER BY score DESC, \"user\"
         ",
                 &[&guild_db_id],
             )
@@ -73,13 +70,14 @@ pub async fn execute(
         rows.iter()
             .map(|row| {
                 let user: i64 = row.get(0);
-                let upvotes: Option<i64> = row.get(1);
-                let downvotes: Option<i64> = row.get(2);
 
                 (
                     UserId(user as u64),
-                    upvotes.unwrap_or_default(),
-                    downvotes.unwrap_or_default(),
+                    row.get::<_, i64>(1),
+                    row.get::<_, i64>(2),
+                    row.get::<_, i64>(3),
+                    row.get::<_, i64>(4),
+                    row.get::<_, i64>(5),
                 )
             })
             .collect()
@@ -134,7 +132,7 @@ async fn show_page(
     ctx: &Context,
     command: &ApplicationCommandInteraction,
     command_config: &Command,
-    top: &Vec<(UserId, i64, i64)>,
+    top: &Vec<(UserId, i64, i64, i64, i64, i64)>,
     index: usize,
     count: usize,
     size: usize,
@@ -173,32 +171,35 @@ async fn show_page(
                 &top[start..end]
             };
 
-            embed.fields(
-                page.iter()
-                    .enumerate()
-                    .map(|(i, (user, upvotes, downvotes))| {
-                        let title = {
-                            let index = start + i;
-
-                            match rank_titles.get(index) {
-                                Some(title) => title.clone(),
-                                None => format!("#{}", index + 1),
-                            }
-                        };
-
-                        (
-                            title,
-                            format!(
-                                "{}: **{}** [+{}, -{}]",
-                                user.mention(),
-                                upvotes - downvotes,
-                                upvotes,
-                                downvotes
-                            ),
-                            false,
-                        )
-                    }),
-            )
+            embed.fields(page.iter().enumerate().map(
+                |(i, (user, upvotes, downvotes, voice, messages, score))| {
+                    let title = {
+                        let index = start + i;
+
+                        match rank_titles.get(index) {
+                            Some(title) => title.clone(),
+                            None => format!("#{}", index + 1),
+                        }
+                    };
+
+                    let mut content = format!(
+                        "{}: **{}** [+{}, -{}",
+                        user.mention(),
+                        score,
+                        upvotes,
+                        downvotes
+                    );
+                    if *voice > 0 {
+                        content.push_str(&format!(", +{} voice", voice));
+                    }
+                    if *messages > 0 {
+                        content.push_str(&format!(", {} messages", messages));
+                    }
+                    content.push(']');
+
+                    (title, content, false)
+                },
+            ))
         },
         vec![row],
     )
@@ -0,0 +1,193 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue::Role,
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    pluralize,
+    strings::ERR_CMD_ARGS_INVALID,
+    utils::{parse_arg, parse_arg_resolved, send_response},
+};
+
+enum Action {
+    Add,
+    Remove,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Action::Add => "Add",
+            Action::Remove => "Remove",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add" => Ok(Action::Add),
+            "remove" => Ok(Action::Remove),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let action = Action::from_str(parse_arg(options, 0)?)?;
+    let name: String = parse_arg(options, 1)?;
+    let mut price = None;
+    let mut role = None;
+    let mut hours = None;
+    for i in 2..options.len() {
+        match options.get(i).unwrap().name.as_str() {
+            "price" => price = Some(parse_arg::<i64>(options, i)?),
+            "role" => {
+                role = match parse_arg_resolved(options, i)? {
+                    Role(role) => Some(role),
+                    _ => unreachable!(),
+                }
+            }
+            "hours" => hours = Some(parse_arg::<i64>(options, i)?),
+            _ => unreachable!(),
+        }
+    }
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    let title = format!("{} shop item {}", action, name);
+
+    match action {
+        Action::Add => {
+            let price = match price {
+                Some(price) => price,
+                None => {
+                    return send_response(
+                        ctx,
+                        command,
+                        command_config,
+                        &title,
+                        "Please provide the price of the item.",
+                    )
+                    .await;
+                }
+            };
+
+            if hours.is_some() && role.is_none() {
+                return send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    "Only roles can be sold for a limited time. Please provide a role.",
+                )
+                .await;
+            }
+
+            // Get role id
+            let role_db_id = match role {
+                Some(role) => Some(database.get_role(guild_id, role.id).await?),
+                None => None,
+            };
+
+            // Insert or update the item
+            database
+                .client
+                .execute(
+                    "
+            INSERT INTO economy_items
+            VALUES ($1::BIGINT, $2::TEXT, $3::BIGINT, $4::BIGINT, $5::BIGINT)
+            ON CONFLICT (guild, name)
+            DO UPDATE SET price = $3::BIGINT, role = $4::BIGINT, hours = $5::BIGINT
+            ",
+                    &[&guild_db_id, &name, &price, &role_db_id, &hours],
+                )
+                .await?;
+
+            let content = match (role, hours) {
+                (Some(role), Some(hours)) => format!(
+                    "Users can now buy the role {} for {} at a price of {}.",
+                    role.mention(),
+                    pluralize!("hour", hours),
+                    price
+                ),
+                (Some(role), None) => format!(
+                    "Users can now buy the role {} at a price of {}.",
+                    role.mention(),
+                    price
+                ),
+                _ => format!(
+                    "Users can now buy the item **{}** at a price of {}.",
+                    name, price
+                ),
+            };
+
+            send_response(ctx, command, command_config, &title, &content).await
+        }
+        Action::Remove => {
+            let modified = database
+                .client
+                .execute(
+                    "
+            DELETE FROM economy_items
+            WHERE guild = $1::BIGINT AND name = $2::TEXT
+            ",
+                    &[&guild_db_id, &name],
+                )
+                .await?;
+
+            if modified == 0 {
+                send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    &format!(
+                        "There is no item called **{}** in the shop. I didn't remove anything.",
+                        name
+                    ),
+                )
+                .await
+            } else {
+                send_response(
+                    ctx,
+                    command,
+                    command_config,
+                    &title,
+                    &format!("I have removed the item **{}** from the shop.", name),
+                )
+                .await
+            }
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serenity::{
+    client::Context,
+    model::interactions::application_command::{
+        ApplicationCommandInteraction,
+        ApplicationCommandInteractionDataOptionValue::{Role, User},
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    pluralize,
+    strings::ERR_CMD_ARGS_INVALID,
+    temporary_roles::{add_temporary_role, remove_temporary_role},
+    utils::{parse_arg, parse_arg_resolved, send_response},
+};
+
+enum Action {
+    Add,
+    Remove,
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Action::Add => "Add",
+            Action::Remove => "Remove",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add" => Ok(Action::Add),
+            "remove" => Ok(Action::Remove),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let action = Action::from_str(parse_arg(options, 0)?)?;
+    let user = match parse_arg_resolved(options, 1)? {
+        User(user, ..) => user,
+        _ => unreachable!(),
+    };
+    let role = match parse_arg_resolved(options, 2)? {
+        Role(role) => role,
+        _ => unreachable!(),
+    };
+    let hours = {
+        if options.len() > 3 {
+            Some(parse_arg::<i64>(options, 3)?)
+        } else {
+            None
+        }
+    };
+
+    let guild_id = command.guild_id.unwrap();
+
+    let title = format!("{} temporary role {} for {}", action, role.name, user.name);
+
+    // Get the member
+    let mut member = guild_id.member(&ctx, user.id).await?;
+
+    match action {
+        Action::Add => {
+            let hours = match hours {
+                Some(hours) => hours,
+                None => {
+                    return send_response(
+                        ctx,
+                        command,
+                        command_config,
+                        &title,
+                        "Please provide the number of hours the user should hold the role.",
+                    )
+                    .await;
+                }
+            };
+
+            if !member.roles.contains(&role.id) {
+                member.add_role(&ctx.http, role.id).await?;
+            }
+
+            add_temporary_role(&database, guild_id, user.id, role.id, hours).await?;
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                &format!(
+                    "The user {} holds the role {} for {} more.",
+                    user.mention(),
+                    role.mention(),
+                    pluralize!("hour", hours)
+                ),
+            )
+            .await
+        }
+        Action::Remove => {
+            if member.roles.contains(&role.id) {
+                member.remove_role(&ctx.http, role.id).await?;
+            }
+
+            remove_temporary_role(&database, guild_id, user.id, role.id).await?;
+
+            send_response(
+                ctx,
+                command,
+                command_config,
+                &title,
+                &format!(
+                    "I have removed the role {} from {}.",
+                    role.mention(),
+                    user.mention()
+                ),
+            )
+            .await
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use serenity::{
+    client::Context,
+    model::{
+        id::{RoleId, UserId},
+        interactions::application_command::{
+            ApplicationCommandInteraction, ApplicationCommandInteractionDataOptionValue::User,
+        },
+    },
+    prelude::Mentionable,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    utils::{parse_arg_resolved, send_response},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse argument
+    let user = if !options.is_empty() {
+        match parse_arg_resolved(options, 0)? {
+            User(user, ..) => Some(user),
+            _ => unreachable!(),
+        }
+    } else {
+        None
+    };
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild and user ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let user_db_id = match user {
+        Some(user) => Some(database.get_user(guild_id, user.id).await?),
+        None => None,
+    };
+
+    // Get the active temporary roles
+    let roles: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT \"user\", role, expires FROM temporary_roles
+                WHERE guild = $1::BIGINT AND expires > NOW()
+                AND ($2::BIGINT IS NULL OR \"user\" = $2::BIGINT)
+                ORDER BY \"user\", expires
+                ",
+                &[&guild_db_id, &user_db_id],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                (
+                    UserId(row.get::<_, i64>(0) as u64),
+                    RoleId(row.get::<_, i64>(1) as u64),
+                    row.get::<_, DateTime<Utc>>(2),
+                )
+            })
+            .collect()
+    };
+
+    let content = roles
+        .iter()
+        .group_by(|(user_id, _, _)| *user_id)
+        .into_iter()
+        .map(|(user_id, roles)| {
+            format!(
+                "{}: {}",
+                user_id.mention(),
+                roles
+                    .map(|(_, role_id, expires)| format!(
+                        "{} (expires <t:{}:R>)",
+                        role_id.mention(),
+                        expires.timestamp()
+                    ))
+                    .join(", ")
+            )
+        })
+        .join("\n");
+
+    let title = match user {
+        Some(user) => format!("Temporary roles of {}", user.name),
+        None => "Temporary roles".to_string(),
+    };
+
+    if content.is_empty() {
+        send_response(
+            ctx,
+            command,
+            command_config,
+            &title,
+            "There are currently no active temporary roles.",
+        )
+        .await
+    } else {
+        send_response(ctx, command, command_config, &title, &content).await
+    }
+}
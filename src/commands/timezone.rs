@@ -0,0 +1,91 @@
+use chrono::Utc;
+use chrono_tz::Tz;
+use serenity::{
+    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    utils::{parse_arg, send_response},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    let title = "Time zone";
+
+    // Show the current time zone if no argument is given
+    if options.is_empty() {
+        let time_zone = database.get_time_zone(command.user.id).await?;
+
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            title,
+            &format!(
+                "Your time zone is {} (it's currently {} there).",
+                time_zone.name(),
+                Utc::now().with_timezone(&time_zone).format("%H:%M")
+            ),
+        )
+        .await;
+    }
+
+    // Parse argument
+    let name = parse_arg::<String>(options, 0)?;
+
+    let time_zone = match name.parse::<Tz>() {
+        Ok(time_zone) => time_zone,
+        Err(_) => {
+            return send_response(
+                ctx,
+                command,
+                command_config,
+                title,
+                &format!(
+                    "I don't know the time zone \"{}\". \
+                    Please use a name like \"Europe/Berlin\" or \"America/New_York\".",
+                    name
+                ),
+            )
+            .await;
+        }
+    };
+
+    database
+        .client
+        .execute(
+            "
+            INSERT INTO user_settings (\"user\", time_zone)
+            VALUES ($1::BIGINT, $2::TEXT)
+            ON CONFLICT (\"user\")
+            DO UPDATE SET time_zone = $2::TEXT
+            ",
+            &[&(command.user.id.0 as i64), &time_zone.name()],
+        )
+        .await?;
+
+    send_response(
+        ctx,
+        command,
+        command_config,
+        title,
+        &format!(
+            "I have set your time zone to {} (it's currently {} there).",
+            time_zone.name(),
+            Utc::now().with_timezone(&time_zone).format("%H:%M")
+        ),
+    )
+    .await
+}
@@ -0,0 +1,82 @@
+use serenity::{
+    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    pluralize,
+    utils::{parse_arg, send_response},
+};
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    let title = "Set voice activity rate";
+
+    if !options.is_empty() {
+        // Parse argument
+        let minutes: i64 = parse_arg(options, 0)?;
+
+        // Insert or update entry
+        database
+            .client
+            .execute(
+                "
+        INSERT INTO voice_rates
+        VALUES ($1::BIGINT, $2::BIGINT)
+        ON CONFLICT (guild)
+        DO UPDATE SET minutes = $2::BIGINT
+        ",
+                &[&guild_db_id, &minutes],
+            )
+            .await?;
+
+        send_response(
+            ctx,
+            command,
+            command_config,
+            title,
+            &format!(
+                "Users will now receive a score of 1 for every {} spent talking in voice channels.",
+                pluralize!("minute", minutes)
+            ),
+        )
+        .await
+    } else {
+        // Delete rate
+        database
+            .client
+            .execute(
+                "
+        DELETE FROM voice_rates
+        WHERE guild = $1::BIGINT
+        ",
+                &[&guild_db_id],
+            )
+            .await?;
+
+        send_response(
+            ctx,
+            command,
+            command_config,
+            title,
+            "Voice activity will no longer be tracked or count towards the score of users.",
+        )
+        .await
+    }
+}
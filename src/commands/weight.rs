@@ -0,0 +1,122 @@
+use std::{
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+
+use serenity::{
+    client::Context, model::interactions::application_command::ApplicationCommandInteraction,
+};
+
+use crate::{
+    config::Command,
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    strings::ERR_CMD_ARGS_INVALID,
+    utils::{parse_arg, send_response},
+};
+
+enum Source {
+    Votes,
+    Messages,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Source::Votes => "votes",
+            Source::Messages => "messages",
+        };
+
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Source {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "votes" => Ok(Source::Votes),
+            "messages" => Ok(Source::Messages),
+            _ => Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+        }
+    }
+}
+
+pub async fn execute(
+    ctx: &Context,
+    command: &ApplicationCommandInteraction,
+    command_config: &Command,
+) -> Result<(), KowalskiError> {
+    // Get database
+    let database = data!(ctx, Database);
+
+    let options = &command.data.options;
+
+    // Parse arguments
+    let source = Source::from_str(parse_arg(options, 0)?)?;
+    let weight: f64 = parse_arg(options, 1)?;
+
+    let title = format!("Set weight of {}", source);
+
+    if !weight.is_finite() || weight < 0.0 {
+        return send_response(
+            ctx,
+            command,
+            command_config,
+            &title,
+            "The weight has to be a non-negative number.",
+        )
+        .await;
+    }
+
+    let guild_id = command.guild_id.unwrap();
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    // Insert or update entry
+    match source {
+        Source::Votes => {
+            database
+                .client
+                .execute(
+                    "
+                    INSERT INTO score_weights (guild, votes)
+                    VALUES ($1::BIGINT, $2::DOUBLE PRECISION)
+                    ON CONFLICT (guild) DO UPDATE SET votes = $2::DOUBLE PRECISION
+                    ",
+                    &[&guild_db_id, &weight],
+                )
+                .await?;
+        }
+        Source::Messages => {
+            database
+                .client
+                .execute(
+                    "
+                    INSERT INTO score_weights (guild, messages)
+                    VALUES ($1::BIGINT, $2::DOUBLE PRECISION)
+                    ON CONFLICT (guild) DO UPDATE SET messages = $2::DOUBLE PRECISION
+                    ",
+                    &[&guild_db_id, &weight],
+                )
+                .await?;
+        }
+    }
+
+    send_response(
+        ctx,
+        command,
+        command_config,
+        &title,
+        &format!(
+            "Each point gained from {} now counts as a score of {}.
+            The level-up roles of users will be updated on their next score change.",
+            source, weight
+        ),
+    )
+    .await
+}
@@ -4,7 +4,8 @@ use linked_hash_map::LinkedHashMap;
 use serde::Deserialize;
 use serenity::{
     model::{
-        channel::ChannelType, interactions::application_command::ApplicationCommandOptionType,
+        channel::ChannelType,
+        interactions::application_command::{ApplicationCommandOptionType, ApplicationCommandType},
         Permissions,
     },
     prelude::TypeMapKey,
@@ -27,6 +28,8 @@ pub struct General {
     pub command_history_size: usize,
     pub autocomplete_size: usize,
     pub default_cooldown: i64,
+    pub default_activity_cooldown: i64,
+    pub default_activity_length: i32,
     pub leaderboard_size: usize,
     pub leaderboard_titles: Vec<String>,
     pub credits_margin: i64,
@@ -43,6 +46,7 @@ pub struct General {
 #[derive(Deserialize)]
 pub struct Command {
     pub command_type: CommandType,
+    pub kind: Option<CommandKind>,
     pub description: String,
     pub module: Option<Module>,
     pub permission: Option<Permissions>,
@@ -51,12 +55,25 @@ pub struct Command {
     pub cost: Option<i64>,
 }
 
+/// Kinds of commands parsed by the config, defaulting to slash commands.
+///
+/// Note: Context menu commands have no description and no options
+#[derive(Clone, Copy, Deserialize, PartialEq)]
+pub enum CommandKind {
+    Slash,
+    User,
+    Message,
+}
+
 /// Types of commands parsed by the config.
 #[derive(Deserialize)]
 pub enum CommandType {
     About,
     Module,
     Modules,
+    TimeZone,
+    Export,
+    Import,
     Ping,
     Clean,
     Guild,
@@ -65,7 +82,18 @@ pub enum CommandType {
     Clear,
     Publish,
     Reminder,
+    RemindMessage,
+    ReminderDelivery,
     Reminders,
+    MyReminders,
+    Announcement,
+    EventReminders,
+    EventNotifications,
+    TempRole,
+    TempRoles,
+    Achievement,
+    Achievements,
+    Activity,
     Cooldown,
     Cooldowns,
     Drop,
@@ -80,13 +108,25 @@ pub enum CommandType {
     LevelUps,
     Moderation,
     Moderations,
+    Nickname,
     Score,
     Scores,
+    Weight,
+    ReactionGroup,
+    ReactionMessage,
+    ReactionRequirement,
     ReactionRole,
     ReactionRoles,
+    ReactionSlots,
+    RoleMenu,
     Mood,
     Oracle,
     Tldr,
+    Voice,
+    Balance,
+    Buy,
+    Rate,
+    Shop,
 }
 
 /// Types of modules parsed by the config.
@@ -97,6 +137,8 @@ pub enum Module {
     Score,
     ReactionRoles,
     Analyze,
+    Voice,
+    Economy,
 }
 
 /// An option of a command.
@@ -127,6 +169,7 @@ pub enum OptionType {
     Role,
     Mentionable,
     Number,
+    Attachment,
 }
 
 /// Types of options parsed by the config
@@ -173,6 +216,16 @@ impl TypeMapKey for Config {
     type Value = Arc<Config>;
 }
 
+impl Into<ApplicationCommandType> for CommandKind {
+    fn into(self) -> ApplicationCommandType {
+        match self {
+            CommandKind::Slash => ApplicationCommandType::ChatInput,
+            CommandKind::User => ApplicationCommandType::User,
+            CommandKind::Message => ApplicationCommandType::Message,
+        }
+    }
+}
+
 impl Into<ApplicationCommandOptionType> for OptionType {
     fn into(self) -> ApplicationCommandOptionType {
         match self {
@@ -186,6 +239,7 @@ impl Into<ApplicationCommandOptionType> for OptionType {
             OptionType::Role => ApplicationCommandOptionType::Role,
             OptionType::Mentionable => ApplicationCommandOptionType::Mentionable,
             OptionType::Number => ApplicationCommandOptionType::Number,
+            OptionType::Attachment => ApplicationCommandOptionType::Attachment,
         }
     }
 }
@@ -1,5 +1,6 @@
 use std::{env, error::Error, sync::Arc};
 
+use chrono_tz::Tz;
 use serenity::{
     model::{
         channel::ReactionType,
@@ -206,6 +207,46 @@ impl Database {
                             ON DELETE CASCADE
                     );
 
+                    CREATE TABLE IF NOT EXISTS score_messages (
+                        guild           BIGINT PRIMARY KEY,
+                        cooldown        BIGINT NOT NULL,
+                        length          INT NOT NULL,
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_message_exclusions (
+                        guild           BIGINT,
+                        channel         BIGINT,
+                        PRIMARY KEY (guild, channel),
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_channels
+                            FOREIGN KEY (guild, channel)
+                            REFERENCES channels(guild, channel)
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_message_activity (
+                        guild           BIGINT,
+                        \"user\"        BIGINT,
+                        messages        BIGINT NOT NULL DEFAULT 0,
+                        last            TIMESTAMP WITH TIME ZONE NOT NULL,
+                        PRIMARY KEY (guild, \"user\"),
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE
+                    );
+
                     CREATE TABLE IF NOT EXISTS score_roles (
                         guild           BIGINT,
                         role            BIGINT,
@@ -221,6 +262,115 @@ impl Database {
                             ON DELETE CASCADE
                     );
 
+                    CREATE TABLE IF NOT EXISTS score_weights (
+                        guild           BIGINT PRIMARY KEY,
+                        votes           DOUBLE PRECISION NOT NULL DEFAULT 1,
+                        messages        DOUBLE PRECISION NOT NULL DEFAULT 1,
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_pickups (
+                        guild           BIGINT,
+                        \"user\"        BIGINT,
+                        pickups         BIGINT NOT NULL DEFAULT 0,
+                        PRIMARY KEY (guild, \"user\"),
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_achievements (
+                        id              SERIAL PRIMARY KEY,
+                        guild           BIGINT NOT NULL,
+                        name            TEXT NOT NULL,
+                        criterion       TEXT NOT NULL,
+                        goal            BIGINT NOT NULL,
+                        days            INT NOT NULL DEFAULT 0,
+                        UNIQUE (guild, name),
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE,
+                        CONSTRAINT unsigned_days
+                            CHECK (days >= 0)
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_achievement_rewards (
+                        achievement     INT PRIMARY KEY,
+                        guild           BIGINT NOT NULL,
+                        role            BIGINT NOT NULL,
+                        CONSTRAINT fk_score_achievements
+                            FOREIGN KEY (achievement)
+                            REFERENCES score_achievements(id)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_roles
+                            FOREIGN KEY (guild, role)
+                            REFERENCES roles(guild, role)
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_achievement_users (
+                        achievement     INT,
+                        guild           BIGINT NOT NULL,
+                        \"user\"        BIGINT,
+                        time            TIMESTAMP WITH TIME ZONE NOT NULL,
+                        PRIMARY KEY (achievement, \"user\"),
+                        CONSTRAINT fk_score_achievements
+                            FOREIGN KEY (achievement)
+                            REFERENCES score_achievements(id)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_achievement_streaks (
+                        achievement     INT,
+                        guild           BIGINT NOT NULL,
+                        \"user\"        BIGINT,
+                        since           TIMESTAMP WITH TIME ZONE NOT NULL,
+                        PRIMARY KEY (achievement, \"user\"),
+                        CONSTRAINT fk_score_achievements
+                            FOREIGN KEY (achievement)
+                            REFERENCES score_achievements(id)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_nicknames (
+                        guild           BIGINT PRIMARY KEY,
+                        source          TEXT NOT NULL,
+                        suffix          BOOLEAN NOT NULL DEFAULT false,
+                        ranking         BIGINT[] NOT NULL DEFAULT '{}',
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_nickname_originals (
+                        guild           BIGINT,
+                        \"user\"        BIGINT,
+                        nickname        TEXT,
+                        PRIMARY KEY (guild, \"user\"),
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE
+                    );
+
                     CREATE TABLE IF NOT EXISTS reaction_roles (
                         guild           BIGINT,
                         channel         BIGINT,
@@ -253,6 +403,83 @@ impl Database {
                             CHECK (slots >= 0)
                     );
 
+                    CREATE TABLE IF NOT EXISTS reaction_role_groups (
+                        guild           BIGINT,
+                        channel         BIGINT,
+                        message         BIGINT,
+                        mode            TEXT NOT NULL,
+                        maximum         INT,
+                        PRIMARY KEY (guild, channel, message),
+                        CONSTRAINT fk_messages
+                            FOREIGN KEY (guild, channel, message)
+                            REFERENCES messages(guild, channel, message)
+                            ON DELETE CASCADE,
+                        CONSTRAINT positive_maximum
+                            CHECK (maximum > 0)
+                    );
+
+                    CREATE TABLE IF NOT EXISTS reaction_role_messages (
+                        guild           BIGINT,
+                        channel         BIGINT,
+                        message         BIGINT,
+                        title           TEXT NOT NULL,
+                        description     TEXT NOT NULL,
+                        PRIMARY KEY (guild, channel, message),
+                        CONSTRAINT fk_messages
+                            FOREIGN KEY (guild, channel, message)
+                            REFERENCES messages(guild, channel, message)
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS reaction_role_requirements (
+                        guild           BIGINT,
+                        role            BIGINT,
+                        requirement     BIGINT,
+                        forbidden       BOOLEAN NOT NULL,
+                        PRIMARY KEY (guild, role, requirement),
+                        CONSTRAINT fk_roles
+                            FOREIGN KEY (guild, role)
+                            REFERENCES roles(guild, role)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_requirements
+                            FOREIGN KEY (guild, requirement)
+                            REFERENCES roles(guild, role)
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS role_menus (
+                        guild           BIGINT,
+                        name            TEXT,
+                        channel         BIGINT NOT NULL,
+                        message         BIGINT NOT NULL,
+                        kind            TEXT NOT NULL,
+                        PRIMARY KEY (guild, name),
+                        CONSTRAINT fk_messages
+                            FOREIGN KEY (guild, channel, message)
+                            REFERENCES messages(guild, channel, message)
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS role_menu_roles (
+                        guild           BIGINT,
+                        name            TEXT,
+                        role            BIGINT,
+                        slots           INT,
+                        capacity        INT CHECK (capacity >= 0),
+                        PRIMARY KEY (guild, name, role),
+                        CONSTRAINT fk_role_menus
+                            FOREIGN KEY (guild, name)
+                            REFERENCES role_menus(guild, name)
+                            ON DELETE CASCADE
+                            ON UPDATE CASCADE,
+                        CONSTRAINT fk_roles
+                            FOREIGN KEY (guild, role)
+                            REFERENCES roles(guild, role)
+                            ON DELETE CASCADE,
+                        CONSTRAINT unsigned_slots
+                            CHECK (slots >= 0)
+                    );
+
                     CREATE TABLE IF NOT EXISTS reminders (
                         guild           BIGINT,
                         channel         BIGINT,
@@ -275,10 +502,247 @@ impl Database {
                         CONSTRAINT fk_users
                             FOREIGN KEY (guild, \"user\")\
                             REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS voice_rates (
+                        guild           BIGINT PRIMARY KEY,
+                        minutes         BIGINT NOT NULL,
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE,
+                        CONSTRAINT positive_minutes
+                            CHECK (minutes > 0)
+                    );
+
+                    CREATE TABLE IF NOT EXISTS voice_activity (
+                        guild           BIGINT,
+                        \"user\"        BIGINT,
+                        minutes         BIGINT NOT NULL DEFAULT 0,
+                        PRIMARY KEY (guild, \"user\"),
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS economy_rates (
+                        guild           BIGINT PRIMARY KEY,
+                        rate            DOUBLE PRECISION NOT NULL,
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE,
+                        CONSTRAINT positive_rate
+                            CHECK (rate > 0)
+                    );
+
+                    CREATE TABLE IF NOT EXISTS economy_items (
+                        guild           BIGINT,
+                        name            TEXT,
+                        price           BIGINT NOT NULL,
+                        role            BIGINT,
+                        hours           BIGINT,
+                        PRIMARY KEY (guild, name),
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_roles
+                            FOREIGN KEY (guild, role)
+                            REFERENCES roles(guild, role)
+                            ON DELETE CASCADE,
+                        CONSTRAINT positive_price
+                            CHECK (price > 0),
+                        CONSTRAINT positive_hours
+                            CHECK (hours > 0),
+                        CONSTRAINT role_for_hours
+                            CHECK (hours IS NULL OR role IS NOT NULL)
+                    );
+
+                    CREATE TABLE IF NOT EXISTS economy_balances (
+                        guild           BIGINT,
+                        \"user\"        BIGINT,
+                        spent           BIGINT NOT NULL DEFAULT 0,
+                        adjustment      BIGINT NOT NULL DEFAULT 0,
+                        PRIMARY KEY (guild, \"user\"),
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS economy_transactions (
+                        id              SERIAL PRIMARY KEY,
+                        guild           BIGINT NOT NULL,
+                        \"user\"        BIGINT NOT NULL,
+                        item            TEXT NOT NULL,
+                        role            BIGINT,
+                        price           BIGINT NOT NULL,
+                        time            TIMESTAMP WITH TIME ZONE NOT NULL,
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
                             ON DELETE CASCADE,
-                        PRIMARY KEY (guild, channel, \"user\", time)
+                        CONSTRAINT fk_roles
+                            FOREIGN KEY (guild, role)
+                            REFERENCES roles(guild, role)
+                            ON DELETE CASCADE
                     );
 
+                    CREATE TABLE IF NOT EXISTS temporary_roles (
+                        guild           BIGINT,
+                        \"user\"        BIGINT,
+                        role            BIGINT,
+                        expires         TIMESTAMP WITH TIME ZONE NOT NULL,
+                        PRIMARY KEY (guild, \"user\", role),
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_roles
+                            FOREIGN KEY (guild, role)
+                            REFERENCES roles(guild, role)
+                            ON DELETE CASCADE
+                    );
+
+                    ALTER TABLE reaction_roles
+                        ADD COLUMN IF NOT EXISTS hours BIGINT CHECK (hours > 0);
+
+                    ALTER TABLE score_roles
+                        ADD COLUMN IF NOT EXISTS hours BIGINT CHECK (hours > 0);
+
+                    ALTER TABLE reaction_roles
+                        ADD COLUMN IF NOT EXISTS capacity INT CHECK (capacity >= 0);
+
+                    CREATE TABLE IF NOT EXISTS reaction_role_waitlist (
+                        guild           BIGINT,
+                        channel         BIGINT,
+                        message         BIGINT,
+                        emoji           INT,
+                        role            BIGINT,
+                        \"user\"        BIGINT,
+                        time            TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+                        PRIMARY KEY (guild, channel, message, emoji, role, \"user\"),
+                        CONSTRAINT fk_reaction_roles
+                            FOREIGN KEY (guild, channel, message, emoji, role)
+                            REFERENCES reaction_roles(guild, channel, message, emoji, role)
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS score_role_grants (
+                        guild           BIGINT,
+                        \"user\"        BIGINT,
+                        role            BIGINT,
+                        PRIMARY KEY (guild, \"user\", role),
+                        CONSTRAINT fk_users
+                            FOREIGN KEY (guild, \"user\")
+                            REFERENCES users(guild, \"user\")
+                            ON DELETE CASCADE,
+                        CONSTRAINT fk_roles
+                            FOREIGN KEY (guild, role)
+                            REFERENCES roles(guild, role)
+                            ON DELETE CASCADE
+                    );
+
+                    ALTER TABLE reminders
+                        ADD COLUMN IF NOT EXISTS recurrence TEXT,
+                        ADD COLUMN IF NOT EXISTS ends TIMESTAMP WITH TIME ZONE,
+                        ADD COLUMN IF NOT EXISTS remaining INT CHECK (remaining >= 0);
+
+                    ALTER TABLE reminders
+                        DROP CONSTRAINT IF EXISTS reminders_pkey,
+                        ALTER COLUMN guild DROP NOT NULL,
+                        ALTER COLUMN channel SET NOT NULL,
+                        ALTER COLUMN \"user\" SET NOT NULL,
+                        ALTER COLUMN time SET NOT NULL,
+                        ADD COLUMN IF NOT EXISTS delivery TEXT,
+                        ADD COLUMN IF NOT EXISTS retry TIMESTAMP WITH TIME ZONE;
+
+                    ALTER TABLE reminders
+                        ADD COLUMN IF NOT EXISTS id SERIAL CONSTRAINT pk_reminders PRIMARY KEY;
+
+                    ALTER TABLE reminders
+                        ADD COLUMN IF NOT EXISTS announcement BOOLEAN NOT NULL DEFAULT FALSE,
+                        ADD COLUMN IF NOT EXISTS role BIGINT,
+                        ADD COLUMN IF NOT EXISTS title TEXT,
+                        ADD COLUMN IF NOT EXISTS image TEXT;
+
+                    CREATE TABLE IF NOT EXISTS user_settings (
+                        \"user\"        BIGINT PRIMARY KEY,
+                        time_zone       TEXT
+                    );
+
+                    ALTER TABLE user_settings
+                        ADD COLUMN IF NOT EXISTS delivery TEXT;
+
+                    ALTER TABLE user_settings
+                        ADD COLUMN IF NOT EXISTS event_reminders BOOLEAN;
+
+                    CREATE TABLE IF NOT EXISTS event_reminder_settings (
+                        guild           BIGINT PRIMARY KEY,
+                        minutes         INT[] NOT NULL,
+                        channel         BIGINT,
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE
+                    );
+
+                    CREATE TABLE IF NOT EXISTS event_reminders_sent (
+                        guild           BIGINT,
+                        event           BIGINT,
+                        start           TIMESTAMP WITH TIME ZONE,
+                        minutes         INT,
+                        PRIMARY KEY (guild, event, start, minutes),
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE
+                    );
+
+                    ALTER TABLE publishing
+                    ADD COLUMN IF NOT EXISTS name TEXT NOT NULL DEFAULT 'all',
+                    ADD COLUMN IF NOT EXISTS channel BIGINT,
+                    ADD COLUMN IF NOT EXISTS keyword TEXT,
+                    ADD COLUMN IF NOT EXISTS creator BIGINT,
+                    DROP CONSTRAINT IF EXISTS publishing_guild_key;
+
+                    CREATE UNIQUE INDEX IF NOT EXISTS publishing_name ON publishing (guild, name);
+
+                    CREATE TABLE IF NOT EXISTS scheduled_events (
+                        guild           BIGINT,
+                        event           BIGINT,
+                        name            TEXT NOT NULL,
+                        description     TEXT,
+                        channel         BIGINT,
+                        location        TEXT,
+                        creator         BIGINT,
+                        creator_name    TEXT,
+                        start           TIMESTAMP WITH TIME ZONE NOT NULL,
+                        \"end\"         TIMESTAMP WITH TIME ZONE,
+                        status          INT NOT NULL,
+                        sequence        INT NOT NULL DEFAULT 0,
+                        modified        TIMESTAMP WITH TIME ZONE NOT NULL,
+                        PRIMARY KEY (guild, event),
+                        CONSTRAINT fk_guilds
+                            FOREIGN KEY (guild)
+                            REFERENCES guilds(guild)
+                            ON DELETE CASCADE
+                    );
+
+                    ALTER TABLE publishing
+                    ADD COLUMN IF NOT EXISTS modified TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW();
+
                     CREATE TABLE IF NOT EXISTS owned_guilds (
                         guild           BIGINT PRIMARY KEY,
                         CONSTRAINT fk_guilds
@@ -286,6 +750,53 @@ impl Database {
                             REFERENCES guilds(guild)
                             ON DELETE CASCADE
                     );
+
+                    DROP VIEW IF EXISTS balances;
+
+                    DROP VIEW IF EXISTS scores;
+
+                    CREATE VIEW scores AS
+                        WITH votes AS (
+                            SELECT r.guild, user_to \"user\",
+                            COUNT(*) FILTER (WHERE upvote) upvotes,
+                            COUNT(*) FILTER (WHERE NOT upvote) downvotes
+                            FROM score_reactions r
+                            INNER JOIN score_emojis se ON r.guild = se.guild AND r.emoji = se.emoji
+                            GROUP BY r.guild, user_to
+                        ), voice AS (
+                            SELECT va.guild, va.\"user\", va.minutes / vr.minutes voice
+                            FROM voice_activity va
+                            INNER JOIN voice_rates vr ON va.guild = vr.guild
+                        )
+
+                        SELECT u.guild, u.\"user\",
+                        COALESCE(upvotes, 0) upvotes,
+                        COALESCE(downvotes, 0) downvotes,
+                        COALESCE(voice, 0) voice,
+                        COALESCE(ma.messages, 0) messages,
+                        ROUND(
+                            COALESCE(w.votes, 1) * (COALESCE(upvotes, 0) - COALESCE(downvotes, 0))
+                            + COALESCE(w.messages, 1) * COALESCE(ma.messages, 0)
+                        )::BIGINT + COALESCE(voice, 0) score
+                        FROM users u
+                        LEFT JOIN votes v ON u.guild = v.guild AND u.\"user\" = v.\"user\"
+                        LEFT JOIN voice vo ON u.guild = vo.guild AND u.\"user\" = vo.\"user\"
+                        LEFT JOIN score_message_activity ma
+                            ON u.guild = ma.guild AND u.\"user\" = ma.\"user\"
+                        LEFT JOIN score_weights w ON u.guild = w.guild
+                        WHERE v.\"user\" IS NOT NULL OR vo.\"user\" IS NOT NULL
+                            OR ma.\"user\" IS NOT NULL;
+
+                    CREATE VIEW balances AS
+                        SELECT s.guild, s.\"user\",
+                        FLOOR(COALESCE(er.rate, 1) * upvotes)::BIGINT
+                            + COALESCE(eb.adjustment, 0) earned,
+                        COALESCE(eb.spent, 0) spent,
+                        FLOOR(COALESCE(er.rate, 1) * upvotes)::BIGINT
+                            + COALESCE(eb.adjustment, 0) - COALESCE(eb.spent, 0) balance
+                        FROM scores s
+                        LEFT JOIN economy_rates er ON s.guild = er.guild
+                        LEFT JOIN economy_balances eb ON s.guild = eb.guild AND s.\"user\" = eb.\"user\";
                 ",
             )
             .await?;
@@ -469,6 +980,24 @@ impl Database {
 
         Ok(row.get(0))
     }
+
+    /// Gets the time zone of a user given the UserId object.
+    ///
+    /// Note: If the user didn't set a time zone, UTC is used
+    pub async fn get_time_zone(&self, user_id: UserId) -> Result<Tz, KowalskiError> {
+        let row = self
+            .client
+            .query_opt(
+                "SELECT time_zone FROM user_settings WHERE \"user\" = $1::BIGINT",
+                &[&(user_id.0 as i64)],
+            )
+            .await?;
+
+        Ok(row
+            .and_then(|row| row.get::<_, Option<String>>(0))
+            .and_then(|time_zone| time_zone.parse().ok())
+            .unwrap_or(Tz::UTC))
+    }
 }
 
 impl TypeMapKey for Database {
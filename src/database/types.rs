@@ -20,6 +20,8 @@ pub struct ModuleStatus {
     pub score: bool,
     pub reaction_roles: bool,
     pub analyze: bool,
+    pub voice: bool,
+    pub economy: bool,
 }
 
 /// A table with all fields resolved to a String.
@@ -41,6 +43,8 @@ impl ModuleStatus {
             score: false,
             reaction_roles: false,
             analyze: false,
+            voice: false,
+            economy: false,
         }
     }
 }
@@ -55,6 +59,8 @@ impl<'a> FromSql<'a> for ModuleStatus {
             score: bits.get(2).unwrap_or_default(),
             reaction_roles: bits.get(3).unwrap_or_default(),
             analyze: bits.get(4).unwrap_or_default(),
+            voice: bits.get(5).unwrap_or_default(),
+            economy: bits.get(6).unwrap_or_default(),
         })
     }
 
@@ -74,6 +80,8 @@ impl ToSql for ModuleStatus {
         bits.set(2, self.score);
         bits.set(3, self.reaction_roles);
         bits.set(4, self.analyze);
+        bits.set(5, self.voice);
+        bits.set(6, self.economy);
 
         bits.to_sql(ty, out)
     }
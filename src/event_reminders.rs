@@ -0,0 +1,393 @@
+use std::{
+    collections::HashSet,
+    future::pending,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use chrono::{DateTime, Utc};
+use serenity::{
+    client::Context,
+    http::UserPagination,
+    model::{
+        guild::ScheduledEventStatus,
+        id::{ChannelId, GuildId, ScheduledEventId, UserId},
+    },
+    prelude::{Mentionable, TypeMapKey},
+    utils::Colour,
+};
+use tokio::{sync::Notify, time::sleep};
+use tracing::error;
+
+use crate::{
+    data, database::client::Database, error::KowalskiError, strings::ERR_EVENT_REMINDER,
+    utils::create_embed,
+};
+
+/// Number of interested users to request at once.
+const USER_PAGE_SIZE: u64 = 100;
+
+/// Maximum length of a message mentioning interested users.
+const MESSAGE_LENGTH: usize = 2000;
+
+/// Maximum number of users a message may ping.
+const MAX_MENTIONS: usize = 100;
+
+/// Number of seconds to wait before retrying to send event reminders.
+const RETRY_SECONDS: i64 = 60;
+
+/// Scheduler waiting for the point in time the next event reminder is due.
+#[derive(Default)]
+pub struct EventReminderScheduler {
+    notify: Notify,
+    started: AtomicBool,
+}
+
+impl EventReminderScheduler {
+    /// Wake the scheduler to look for the next event reminder again, e.g. after events changed.
+    pub fn reschedule(&self) {
+        self.notify.notify_one();
+    }
+}
+
+impl TypeMapKey for EventReminderScheduler {
+    type Value = Arc<EventReminderScheduler>;
+}
+
+pub fn check_event_reminders(ctx: Context) {
+    tokio::spawn(async move {
+        // Get database and scheduler
+        let (database, scheduler) = data!(ctx, (Database, EventReminderScheduler));
+
+        // The ready event fires again after reconnecting, but only one scheduler may run
+        if scheduler.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        loop {
+            // Send due event reminders, including the ones missed while being offline
+            let retry = Utc::now() + chrono::Duration::seconds(RETRY_SECONDS);
+            let next = match send_event_reminders(&ctx, &database).await {
+                Ok(complete) => get_next_event_reminder(&database).await.map(|next| {
+                    // Retry the event reminders which failed later on
+                    if complete {
+                        next
+                    } else {
+                        Some(next.map_or(retry, |next| next.min(retry)))
+                    }
+                }),
+                Err(why) => Err(why),
+            }
+            .unwrap_or_else(|why| {
+                error!("{}: {:?}", ERR_EVENT_REMINDER, why);
+                Some(retry)
+            });
+
+            // Sleep until the next event reminder is due, waking up early if events changed
+            let sleep_until = async {
+                match next {
+                    Some(next) => sleep((next - Utc::now()).to_std().unwrap_or_default()).await,
+                    None => pending().await,
+                }
+            };
+
+            tokio::select! {
+                _ = sleep_until => {}
+                _ = scheduler.notify.notified() => {}
+            }
+        }
+    });
+}
+
+async fn get_next_event_reminder(
+    database: &Database,
+) -> Result<Option<DateTime<Utc>>, KowalskiError> {
+    let row = database
+        .client
+        .query_one(
+            "
+            SELECT MIN(e.start - make_interval(mins => m.minutes))
+            FROM scheduled_events e
+            INNER JOIN event_reminder_settings s ON s.guild = e.guild
+            CROSS JOIN LATERAL unnest(s.minutes) AS m(minutes)
+            WHERE e.status = $1::INT AND e.start - make_interval(mins => m.minutes) > $2::TIMESTAMPTZ
+            ",
+            &[&(ScheduledEventStatus::Scheduled as i32), &Utc::now()],
+        )
+        .await?;
+
+    Ok(row.get(0))
+}
+
+// Send the due event reminders of all guilds, returning whether none of them failed
+async fn send_event_reminders(ctx: &Context, database: &Database) -> Result<bool, KowalskiError> {
+    // Get guilds which enabled event reminders
+    let rows = database
+        .client
+        .query(
+            "SELECT guild, minutes, channel FROM event_reminder_settings",
+            &[],
+        )
+        .await?;
+
+    let mut complete = true;
+
+    for row in rows {
+        let guild_id = GuildId(row.get::<_, i64>(0) as u64);
+        let minutes = row.get::<_, Vec<i32>>(1);
+        let channel_id = row
+            .get::<_, Option<i64>>(2)
+            .map(|channel| ChannelId(channel as u64));
+
+        match remind_guild(ctx, database, guild_id, &minutes, channel_id).await {
+            Ok(guild_complete) => complete &= guild_complete,
+            Err(why) => {
+                error!("{}: {:?}", ERR_EVENT_REMINDER, why);
+                complete = false;
+            }
+        }
+    }
+
+    Ok(complete)
+}
+
+// Send the due event reminders of a guild, returning whether none of them failed
+async fn remind_guild(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    minutes: &[i32],
+    channel_id: Option<ChannelId>,
+) -> Result<bool, KowalskiError> {
+    // Get the scheduled events of the guild
+    let events: Vec<_> = database
+        .client
+        .query(
+            "
+            SELECT event, name, start FROM scheduled_events
+            WHERE guild = $1::BIGINT AND status = $2::INT
+            ",
+            &[
+                &(guild_id.0 as i64),
+                &(ScheduledEventStatus::Scheduled as i32),
+            ],
+        )
+        .await?
+        .iter()
+        .map(|row| {
+            (
+                ScheduledEventId(row.get::<_, i64>(0) as u64),
+                row.get::<_, String>(1),
+                row.get::<_, DateTime<Utc>>(2),
+            )
+        })
+        .collect();
+    let now = Utc::now();
+    let mut complete = true;
+
+    for (event_id, name, start) in &events {
+        if *start <= now {
+            continue;
+        }
+
+        // Only send the reminder of the latest offset which passed, so missed ones don't pile up
+        let due = minutes
+            .iter()
+            .filter(|minutes| *start - chrono::Duration::minutes(**minutes as i64) <= now)
+            .min();
+        let due = match due {
+            Some(due) => *due,
+            None => continue,
+        };
+
+        // Skip reminders which have been sent already
+        let sent = database
+            .client
+            .query_opt(
+                "
+                SELECT * FROM event_reminders_sent
+                WHERE guild = $1::BIGINT AND event = $2::BIGINT AND start = $3::TIMESTAMPTZ
+                AND minutes = $4::INT
+                ",
+                &[&(guild_id.0 as i64), &(event_id.0 as i64), start, &due],
+            )
+            .await?
+            .is_some();
+        if sent {
+            continue;
+        }
+
+        // A single failing event shouldn't keep the others from being reminded
+        if let Err(why) =
+            remind_event(ctx, database, guild_id, *event_id, name, *start, channel_id).await
+        {
+            error!("{}: {:?}", ERR_EVENT_REMINDER, why);
+            complete = false;
+            continue;
+        }
+
+        // Remember the reminder once it has been sent
+        database
+            .client
+            .execute(
+                "
+                INSERT INTO event_reminders_sent
+                VALUES ($1::BIGINT, $2::BIGINT, $3::TIMESTAMPTZ, $4::INT)
+                ON CONFLICT DO NOTHING
+                ",
+                &[&(guild_id.0 as i64), &(event_id.0 as i64), start, &due],
+            )
+            .await?;
+    }
+
+    // Forget about events which are not upcoming anymore
+    let event_ids: Vec<i64> = events
+        .iter()
+        .map(|(event_id, ..)| event_id.0 as i64)
+        .collect();
+    database
+        .client
+        .execute(
+            "
+            DELETE FROM event_reminders_sent
+            WHERE guild = $1::BIGINT AND NOT event = ANY($2::BIGINT[])
+            ",
+            &[&(guild_id.0 as i64), &event_ids],
+        )
+        .await?;
+
+    Ok(complete)
+}
+
+async fn remind_event(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    event_id: ScheduledEventId,
+    name: &str,
+    start: DateTime<Utc>,
+    channel_id: Option<ChannelId>,
+) -> Result<(), KowalskiError> {
+    let user_ids = get_interested_users(ctx, database, guild_id, event_id).await?;
+
+    if user_ids.is_empty() {
+        return Ok(());
+    }
+
+    let link = format!("https://discord.com/events/{}/{}", guild_id.0, event_id.0);
+    let content = format!("[{}]({}) starts <t:{}:R>!", name, link, start.timestamp());
+
+    match channel_id {
+        // Mention the users in the channel, splitting the mentions over several messages
+        Some(channel_id) => {
+            let mut messages = vec![(content.clone(), Vec::new())];
+            for user_id in &user_ids {
+                let mention = format!(" {}", user_id.mention());
+                let (message, mentioned) = messages.last().unwrap();
+                if message.len() + mention.len() > MESSAGE_LENGTH || mentioned.len() >= MAX_MENTIONS
+                {
+                    messages.push((String::new(), Vec::new()));
+                }
+
+                let (message, mentioned) = messages.last_mut().unwrap();
+                message.push_str(&mention);
+                mentioned.push(*user_id);
+            }
+
+            for (i, (message, mentioned)) in messages.into_iter().enumerate() {
+                // Only ping the users mentioned in this message
+                let result = channel_id
+                    .send_message(&ctx.http, |create| {
+                        create
+                            .content(message)
+                            .allowed_mentions(|mentions| mentions.users(mentioned))
+                    })
+                    .await;
+
+                match result {
+                    Ok(_) => {}
+                    Err(why) if i == 0 => return Err(why.into()),
+                    // Earlier messages went out already, so their users shouldn't be pinged again
+                    Err(why) => {
+                        error!("{}: {:?}", ERR_EVENT_REMINDER, why);
+                        break;
+                    }
+                }
+            }
+        }
+        // Send a direct message to each user
+        None => {
+            let guild_name = guild_id.name(ctx).unwrap_or_else(|| "a guild".to_string());
+
+            for user_id in user_ids {
+                let mut embed = create_embed(
+                    "Event reminder",
+                    &format!("{}\n\nThe event takes place on {}.", content, guild_name),
+                );
+                embed.color(Colour::BLURPLE);
+
+                let result = match user_id.create_dm_channel(&ctx.http).await {
+                    Ok(channel) => channel
+                        .send_message(&ctx.http, |message| message.set_embeds(vec![embed]))
+                        .await
+                        .map(|_| ()),
+                    Err(why) => Err(why),
+                };
+
+                // Users may not accept direct messages, which shouldn't affect the others
+                if let Err(why) = result {
+                    error!("{}: {:?}", ERR_EVENT_REMINDER, why);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Get the users interested in an event, except the ones who opted out of event reminders
+async fn get_interested_users(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    event_id: ScheduledEventId,
+) -> Result<Vec<UserId>, KowalskiError> {
+    let mut user_ids = Vec::new();
+    let mut after = None;
+
+    loop {
+        let users = guild_id
+            .scheduled_event_users_optioned(&ctx.http, event_id, Some(USER_PAGE_SIZE), after, None)
+            .await?;
+
+        let count = users.len() as u64;
+        user_ids.extend(users.iter().map(|user| user.user.id));
+
+        match user_ids.last() {
+            Some(last) if count == USER_PAGE_SIZE => after = Some(UserPagination::After(*last)),
+            _ => break,
+        }
+    }
+
+    let raw_ids: Vec<i64> = user_ids.iter().map(|user_id| user_id.0 as i64).collect();
+    let opted_out: HashSet<u64> = database
+        .client
+        .query(
+            "
+            SELECT \"user\" FROM user_settings
+            WHERE \"user\" = ANY($1::BIGINT[]) AND NOT event_reminders
+            ",
+            &[&raw_ids],
+        )
+        .await?
+        .iter()
+        .map(|row| row.get::<_, i64>(0) as u64)
+        .collect();
+
+    Ok(user_ids
+        .into_iter()
+        .filter(|user_id| !opted_out.contains(&user_id.0))
+        .collect())
+}
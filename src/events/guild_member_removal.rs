@@ -11,12 +11,16 @@ use serenity::{
     },
     prelude::Mentionable,
 };
+use tracing::error;
 
 use crate::{
+    achievements::check_achievements,
     config::Config,
     data,
     database::{client::Database, types::ModuleStatus},
     error::KowalskiError,
+    reaction_slots::reconcile_slots,
+    strings::ERR_ACHIEVEMENTS,
     utils::create_embed,
 };
 
@@ -47,6 +51,11 @@ pub async fn guild_member_removal(
         .await?
         .map_or(ModuleStatus::default(), |row| row.get(0));
 
+    // Free the reaction-role slots held by the user
+    if status.reaction_roles {
+        reconcile_slots(ctx, &database, guild_id).await?;
+    }
+
     // Check if the score module is enabled
     if status.score {
         // Select a random channel to send the message to
@@ -154,6 +163,27 @@ pub async fn guild_member_removal(
                         )
                         .await?;
 
+                    // Count the pickup of the user
+                    database
+                        .client
+                        .execute(
+                            "
+                            INSERT INTO score_pickups
+                            VALUES ($1::BIGINT, $2::BIGINT, 1)
+                            ON CONFLICT (guild, \"user\")
+                            DO UPDATE SET pickups = score_pickups.pickups + 1
+                            ",
+                            &[&guild_db_id, &interaction_user_db_id],
+                        )
+                        .await?;
+
+                    // Check the achievements of the user
+                    if let Err(why) =
+                        check_achievements(ctx, &database, guild_id, interaction.user.id).await
+                    {
+                        error!("{}: {:?}", ERR_ACHIEVEMENTS, why);
+                    }
+
                     let embed = create_embed(
                         &title,
                         &format!(
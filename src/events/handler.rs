@@ -3,8 +3,9 @@ use std::collections::HashMap;
 use serenity::{
     async_trait,
     client::{Context, EventHandler},
+    json::Value,
     model::{
-        channel::{GuildChannel, Reaction},
+        channel::{GuildChannel, Message, Reaction},
         gateway::Ready,
         guild::{Emoji, Guild, Member, Role, UnavailableGuild},
         id::{ChannelId, EmojiId, GuildId, MessageId, RoleId},
@@ -22,11 +23,13 @@ use crate::{
         guild_member_removal::guild_member_removal,
         guild_role_delete::guild_role_delete,
         interaction_create::interaction_create,
+        message::message,
         message_delete::{message_delete, message_delete_bulk},
         reaction::{reaction_add, reaction_remove, reaction_remove_all},
         ready::ready,
+        scheduled_event::scheduled_event,
     },
-    strings::{ERR_MEMBER_REMOVAL, ERR_REACTION},
+    strings::{ERR_MEMBER_REMOVAL, ERR_MESSAGE, ERR_REACTION, ERR_SCHEDULED_EVENT},
 };
 
 pub struct Handler;
@@ -81,6 +84,12 @@ impl EventHandler for Handler {
         .unwrap()
     }
 
+    async fn message(&self, ctx: Context, new_message: Message) {
+        if let Err(why) = message(&ctx, new_message).await {
+            error!("{}: {:?}", ERR_MESSAGE, why);
+        }
+    }
+
     async fn message_delete(
         &self,
         ctx: Context,
@@ -135,4 +144,10 @@ impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         interaction_create(&ctx, interaction).await
     }
+
+    async fn unknown(&self, ctx: Context, name: String, raw: Value) {
+        if let Err(why) = scheduled_event(&ctx, &name, raw).await {
+            error!("{}: {:?}", ERR_SCHEDULED_EVENT, why);
+        }
+    }
 }
@@ -1,3 +1,4 @@
+use chrono_tz::TZ_VARIANTS;
 use serenity::{
     client::Context,
     model::interactions::{
@@ -16,6 +17,8 @@ use crate::{
     data,
     error::KowalskiError,
     history::History,
+    reminders::{answer_reminder, EDIT_REMINDER_ID, PICK_TIME_ID, REMINDER_ID},
+    role_menus::{answer_role_menu, ROLE_MENU_ID},
     strings::{
         ERR_AUTOCOMPLETE, ERR_CMD_EXECUTION, ERR_MESSAGE_COMPONENT, ERR_USER_EXECUTION_FAILED,
         ERR_USER_TITLE,
@@ -129,6 +132,9 @@ async fn execute_command(
                 CommandType::About => about::execute(ctx, command, command_config).await,
                 CommandType::Module => module::execute(ctx, command, command_config).await,
                 CommandType::Modules => modules::execute(ctx, command, command_config).await,
+                CommandType::TimeZone => timezone::execute(ctx, command, command_config).await,
+                CommandType::Export => export::execute(ctx, command, command_config).await,
+                CommandType::Import => import::execute(ctx, command, command_config).await,
                 CommandType::Ping => ping::execute(ctx, command, command_config).await,
                 CommandType::Clean => clean::execute(ctx, command, command_config).await,
                 CommandType::Guild => guild::execute(ctx, command, command_config).await,
@@ -140,7 +146,34 @@ async fn execute_command(
                 #[cfg(not(feature = "event-calendar"))]
                 CommandType::Publish => disabled::execute(ctx, command, command_config).await,
                 CommandType::Reminder => reminder::execute(ctx, command, command_config).await,
+                CommandType::RemindMessage => {
+                    remindmessage::execute(ctx, command, command_config).await
+                }
+                CommandType::ReminderDelivery => {
+                    reminderdelivery::execute(ctx, command, command_config).await
+                }
                 CommandType::Reminders => reminders::execute(ctx, command, command_config).await,
+                CommandType::MyReminders => {
+                    myreminders::execute(ctx, command, command_config).await
+                }
+                CommandType::Announcement => {
+                    announcement::execute(ctx, command, command_config).await
+                }
+                CommandType::EventReminders => {
+                    eventreminders::execute(ctx, command, command_config).await
+                }
+                CommandType::EventNotifications => {
+                    eventnotifications::execute(ctx, command, command_config).await
+                }
+                CommandType::TempRole => temprole::execute(ctx, command, command_config).await,
+                CommandType::TempRoles => temproles::execute(ctx, command, command_config).await,
+                CommandType::Achievement => {
+                    achievement::execute(ctx, command, command_config).await
+                }
+                CommandType::Achievements => {
+                    achievements::execute(ctx, command, command_config).await
+                }
+                CommandType::Activity => activity::execute(ctx, command, command_config).await,
                 CommandType::Cooldown => cooldown::execute(ctx, command, command_config).await,
                 CommandType::Cooldowns => cooldowns::execute(ctx, command, command_config).await,
                 CommandType::Drop => drop::execute(ctx, command, command_config).await,
@@ -154,17 +187,32 @@ async fn execute_command(
                 CommandType::LevelUp => levelup::execute(ctx, command, command_config).await,
                 CommandType::LevelUps => levelups::execute(ctx, command, command_config).await,
                 CommandType::Moderation => moderation::execute(ctx, command, command_config).await,
+                CommandType::Nickname => nickname::execute(ctx, command, command_config).await,
                 CommandType::Moderations => {
                     moderations::execute(ctx, command, command_config).await
                 }
                 CommandType::Score => score::execute(ctx, command, command_config).await,
                 CommandType::Scores => scores::execute(ctx, command, command_config).await,
+                CommandType::Weight => weight::execute(ctx, command, command_config).await,
+                CommandType::ReactionGroup => {
+                    reactiongroup::execute(ctx, command, command_config).await
+                }
+                CommandType::ReactionMessage => {
+                    reactionmessage::execute(ctx, command, command_config).await
+                }
+                CommandType::ReactionRequirement => {
+                    reactionrequirement::execute(ctx, command, command_config).await
+                }
                 CommandType::ReactionRole => {
                     reactionrole::execute(ctx, command, command_config).await
                 }
                 CommandType::ReactionRoles => {
                     reactionroles::execute(ctx, command, command_config).await
                 }
+                CommandType::ReactionSlots => {
+                    reactionslots::execute(ctx, command, command_config).await
+                }
+                CommandType::RoleMenu => rolemenu::execute(ctx, command, command_config).await,
                 #[cfg(feature = "nlp-model")]
                 CommandType::Mood => mood::execute(ctx, command, command_config).await,
                 #[cfg(feature = "nlp-model")]
@@ -175,6 +223,11 @@ async fn execute_command(
                 CommandType::Mood | CommandType::Oracle | CommandType::Tldr => {
                     disabled::execute(ctx, command, command_config).await
                 }
+                CommandType::Voice => voice::execute(ctx, command, command_config).await,
+                CommandType::Balance => balance::execute(ctx, command, command_config).await,
+                CommandType::Buy => buy::execute(ctx, command, command_config).await,
+                CommandType::Rate => rate::execute(ctx, command, command_config).await,
+                CommandType::Shop => shop::execute(ctx, command, command_config).await,
             }
         }
     }
@@ -208,7 +261,18 @@ async fn answer_autocomplete(
         (option_name, written)
     };
 
-    let choices: Vec<String> = {
+    let choices: Vec<String> = if option_name == "zone" {
+        // Suggest time zones instead of previous entries
+        let written = written.as_str().unwrap().to_lowercase();
+
+        TZ_VARIANTS
+            .iter()
+            .map(|time_zone| time_zone.name())
+            .filter(|name| name.to_lowercase().contains(&written))
+            .map(|name| name.to_string())
+            .take(config.general.autocomplete_size)
+            .collect()
+    } else {
         let history = history_lock.read().await;
 
         history
@@ -244,6 +308,19 @@ async fn answer_message_component(
     ctx: &Context,
     message_component: MessageComponentInteraction,
 ) -> Result<(), KowalskiError> {
+    // Role menus and reminders are answered directly, all other components are handled by their
+    // collectors
+    if message_component.data.custom_id.starts_with(ROLE_MENU_ID) {
+        return answer_role_menu(ctx, &message_component).await;
+    }
+    if message_component.data.custom_id.starts_with(REMINDER_ID) {
+        return answer_reminder(ctx, &message_component).await;
+    }
+    // Editing a reminder or picking its time may open a modal, which is done by the collector
+    if [EDIT_REMINDER_ID, PICK_TIME_ID].contains(&message_component.data.custom_id.as_str()) {
+        return Ok(());
+    }
+
     message_component
         .create_interaction_response(&ctx.http, |response| {
             response.kind(InteractionResponseType::DeferredUpdateMessage)
@@ -0,0 +1,113 @@
+use serenity::{client::Context, model::channel::Message};
+
+use crate::{
+    data,
+    database::{client::Database, types::ModuleStatus},
+    error::KowalskiError,
+    events::reaction::update_roles,
+};
+
+pub async fn message(ctx: &Context, new_message: Message) -> Result<(), KowalskiError> {
+    // Only messages of humans on guilds count
+    let guild_id = match new_message.guild_id {
+        Some(guild_id) if !new_message.author.bot => guild_id,
+        _ => return Ok(()),
+    };
+
+    // Get database
+    let database = data!(ctx, Database);
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    // Get guild status
+    let status = database
+        .client
+        .query_opt(
+            "
+                SELECT status
+                FROM modules
+                WHERE guild = $1::BIGINT
+                ",
+            &[&guild_db_id],
+        )
+        .await?
+        .map_or(ModuleStatus::default(), |row| row.get(0));
+
+    if !status.score {
+        return Ok(());
+    }
+
+    // Get the message activity settings of the guild
+    let settings = {
+        let row = database
+            .client
+            .query_opt(
+                "
+                SELECT cooldown, length
+                FROM score_messages
+                WHERE guild = $1::BIGINT
+                ",
+                &[&guild_db_id],
+            )
+            .await?;
+
+        row.map(|row| (row.get::<_, i64>(0), row.get::<_, i32>(1)))
+    };
+
+    let (cooldown, length) = match settings {
+        Some(settings) => settings,
+        None => return Ok(()),
+    };
+
+    // Ignore messages which are too short, if their content is available
+    // Note: The content is only received with the message content intent of the NLP model
+    if cfg!(feature = "nlp-model") && new_message.content.trim().chars().count() < length as usize {
+        return Ok(());
+    }
+
+    // Ignore messages in excluded channels
+    let excluded = database
+        .client
+        .query_opt(
+            "
+            SELECT * FROM score_message_exclusions
+            WHERE guild = $1::BIGINT AND channel = $2::BIGINT
+            ",
+            &[&guild_db_id, &(new_message.channel_id.0 as i64)],
+        )
+        .await?
+        .is_some();
+
+    if excluded {
+        return Ok(());
+    }
+
+    // Get user id
+    let user_db_id = database.get_user(guild_id, new_message.author.id).await?;
+
+    // Award the message if the cooldown of the user has expired
+    let awarded = database
+        .client
+        .query_opt(
+            "
+            INSERT INTO score_message_activity
+            VALUES ($1::BIGINT, $2::BIGINT, 1, NOW())
+            ON CONFLICT (guild, \"user\")
+            DO UPDATE SET messages = score_message_activity.messages + 1, last = NOW()
+            WHERE score_message_activity.last + $3::BIGINT * INTERVAL '1 second' <= NOW()
+            RETURNING messages
+            ",
+            &[&guild_db_id, &user_db_id, &cooldown],
+        )
+        .await?
+        .is_some();
+
+    if awarded {
+        // Update the roles of the user
+        let mut member = guild_id.member(&ctx, new_message.author.id).await?;
+        update_roles(ctx, &database, &mut member).await?;
+    }
+
+    Ok(())
+}
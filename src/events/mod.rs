@@ -5,6 +5,8 @@ pub mod guild_member_removal;
 pub mod guild_role_delete;
 pub mod handler;
 pub mod interaction_create;
+pub mod message;
 pub mod message_delete;
 pub mod reaction;
 pub mod ready;
+pub mod scheduled_event;
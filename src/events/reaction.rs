@@ -1,3 +1,4 @@
+use itertools::Itertools;
 use serenity::{
     client::Context,
     model::{
@@ -5,14 +6,22 @@ use serenity::{
         guild::Member,
         id::{ChannelId, GuildId, MessageId, RoleId, UserId},
     },
+    prelude::Mentionable,
 };
+use tracing::error;
 
 use crate::{
+    achievements::check_achievements,
     config::Config,
     cooldowns::Cooldowns,
     data,
     database::{client::Database, types::ModuleStatus},
     error::KowalskiError,
+    nicknames::update_nicknames,
+    reaction_slots::{fill_waitlist, notify},
+    role_requirements::check_requirements,
+    strings::ERR_ACHIEVEMENTS,
+    temporary_roles::{add_temporary_role, remove_temporary_role},
 };
 
 pub async fn reaction_add(ctx: &Context, add_reaction: Reaction) -> Result<(), KowalskiError> {
@@ -54,7 +63,7 @@ pub async fn reaction_add(ctx: &Context, add_reaction: Reaction) -> Result<(), K
                 .client
                 .query(
                     "
-                    SELECT role, slots FROM reaction_roles
+                    SELECT role, slots, hours FROM reaction_roles
                     WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
                     AND emoji = $4::INT
                     ",
@@ -67,6 +76,7 @@ pub async fn reaction_add(ctx: &Context, add_reaction: Reaction) -> Result<(), K
                     (
                         RoleId(row.get::<_, i64>(0) as u64),
                         row.get::<_, Option<i32>>(1),
+                        row.get::<_, Option<i64>>(2),
                     )
                 })
                 .collect()
@@ -108,8 +118,51 @@ pub async fn reaction_add(ctx: &Context, add_reaction: Reaction) -> Result<(), K
             // Remove the reaction
             add_reaction.delete(&ctx.http).await?;
 
-            for (role, slots) in reaction_roles {
+            // Get the group mode of the message
+            let group = database
+                .client
+                .query_opt(
+                    "
+                    SELECT mode, maximum FROM reaction_role_groups
+                    WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                    ",
+                    &[&guild_db_id, &channel_db_id, &message_db_id],
+                )
+                .await?
+                .map(|row| (row.get::<_, String>(0), row.get::<_, Option<i32>>(1)));
+
+            // Get all roles of the group
+            let group_roles: Vec<_> = if group.is_some() {
+                let rows = database
+                    .client
+                    .query(
+                        "
+                        SELECT role, emoji FROM reaction_roles
+                        WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                        ",
+                        &[&guild_db_id, &channel_db_id, &message_db_id],
+                    )
+                    .await?;
+
+                rows.iter()
+                    .map(|row| (RoleId(row.get::<_, i64>(0) as u64), row.get::<_, i32>(1)))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            let (mode, maximum) = match &group {
+                Some((mode, maximum)) => (mode.as_str(), *maximum),
+                None => ("", None),
+            };
+
+            for (role, slots, hours) in reaction_roles {
                 if member.roles.contains(&role) {
+                    // Roles of verify groups are never removed
+                    if mode == "verify" {
+                        continue;
+                    }
+
                     // Increment slots
                     database
                         .client
@@ -126,8 +179,91 @@ pub async fn reaction_add(ctx: &Context, add_reaction: Reaction) -> Result<(), K
 
                     // Remove role from user
                     member.remove_role(&ctx.http, role).await?;
+                    remove_temporary_role(&database, guild_id, user_from_id, role).await?;
+
+                    // Hand the freed slot to the next user on the waitlist
+                    fill_waitlist(
+                        ctx,
+                        &database,
+                        guild_id,
+                        (channel_db_id, message_db_id, emoji_db_id),
+                        role,
+                    )
+                    .await?;
                 } else {
+                    // Tell the user why they can't pick the role
+                    if let Some(reason) =
+                        check_requirements(&database, guild_id, &member, role).await?
+                    {
+                        notify(ctx, user_from_id, &reason).await;
+
+                        continue;
+                    }
+
                     if !matches!(slots, Some(0)) {
+                        // Roles of the group the user currently holds
+                        let held: Vec<_> = group_roles
+                            .iter()
+                            .filter(|(group_role, _)| member.roles.contains(group_role))
+                            .copied()
+                            .collect();
+
+                        match mode {
+                            "unique" => {
+                                // Picking a role of a unique group replaces the other ones
+                                for (held_role, held_emoji) in held
+                                    .into_iter()
+                                    .filter(|(_, held_emoji)| *held_emoji != emoji_db_id)
+                                {
+                                    database
+                                        .client
+                                        .execute(
+                                            "
+                                    UPDATE reaction_roles
+                                    SET slots = slots + 1
+                                    WHERE guild = $1::BIGINT AND channel = $2::BIGINT
+                                    AND message = $3::BIGINT AND emoji = $4::INT
+                                    AND role = $5::BIGINT AND slots IS NOT NULL
+                                    ",
+                                            &[
+                                                &guild_db_id,
+                                                &channel_db_id,
+                                                &message_db_id,
+                                                &held_emoji,
+                                                &(held_role.0 as i64),
+                                            ],
+                                        )
+                                        .await?;
+
+                                    member.remove_role(&ctx.http, held_role).await?;
+                                    remove_temporary_role(
+                                        &database,
+                                        guild_id,
+                                        user_from_id,
+                                        held_role,
+                                    )
+                                    .await?;
+                                    fill_waitlist(
+                                        ctx,
+                                        &database,
+                                        guild_id,
+                                        (channel_db_id, message_db_id, held_emoji),
+                                        held_role,
+                                    )
+                                    .await?;
+                                }
+                            }
+                            "limit" => {
+                                // Users can't pick more roles than the limit allows, if there is one
+                                let held = held.iter().map(|(role, _)| role).unique().count();
+
+                                if maximum.is_some_and(|maximum| held as i32 >= maximum) {
+                                    continue;
+                                }
+                            }
+                            _ => {}
+                        }
+
                         // Decrement slots
                         database
                             .client
@@ -144,6 +280,85 @@ pub async fn reaction_add(ctx: &Context, add_reaction: Reaction) -> Result<(), K
 
                         // Add role to user
                         member.add_role(&ctx.http, role).await?;
+
+                        // Schedule the removal of temporary roles
+                        if let Some(hours) = hours {
+                            add_temporary_role(&database, guild_id, user_from_id, role, hours)
+                                .await?;
+                        }
+                    } else {
+                        // Reacting again to a full role leaves the waitlist
+                        let left = database
+                            .client
+                            .execute(
+                                "
+                        DELETE FROM reaction_role_waitlist
+                        WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                        AND emoji = $4::INT AND role = $5::BIGINT AND \"user\" = $6::BIGINT
+                        ",
+                                &[
+                                    &guild_db_id,
+                                    &channel_db_id,
+                                    &message_db_id,
+                                    &emoji_db_id,
+                                    &(role.0 as i64),
+                                    &user_from_db_id,
+                                ],
+                            )
+                            .await?;
+
+                        if left > 0 {
+                            notify(
+                                ctx,
+                                user_from_id,
+                                &format!(
+                                    "You have left the waitlist of the role {}.",
+                                    role.mention()
+                                ),
+                            )
+                            .await;
+
+                            continue;
+                        }
+
+                        // Join the waitlist and get the position on it
+                        let position: i64 = database
+                            .client
+                            .query_one(
+                                "
+                        WITH inserted AS (
+                            INSERT INTO reaction_role_waitlist
+                            VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::INT, $5::BIGINT,
+                                $6::BIGINT)
+                        )
+
+                        SELECT COUNT(*) + 1 FROM reaction_role_waitlist
+                        WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                        AND emoji = $4::INT AND role = $5::BIGINT
+                        ",
+                                &[
+                                    &guild_db_id,
+                                    &channel_db_id,
+                                    &message_db_id,
+                                    &emoji_db_id,
+                                    &(role.0 as i64),
+                                    &user_from_db_id,
+                                ],
+                            )
+                            .await?
+                            .get(0);
+
+                        notify(
+                            ctx,
+                            user_from_id,
+                            &format!(
+                                "There are no slots left for the role {}. You are number {} on the \
+                                waitlist and will receive the role once a slot becomes available.",
+                                role.mention(),
+                                position
+                            ),
+                        )
+                        .await;
                     }
                 }
             }
@@ -207,6 +422,14 @@ pub async fn reaction_add(ctx: &Context, add_reaction: Reaction) -> Result<(), K
                 let mut member = guild.member(&ctx, user_to_id.0).await?;
                 update_roles(&ctx, &database, &mut member).await?;
 
+                // Check the achievements of both users
+                if let Err(why) = check_achievements(ctx, &database, guild, user_from_id).await {
+                    error!("{}: {:?}", ERR_ACHIEVEMENTS, why);
+                }
+                if let Err(why) = check_achievements(ctx, &database, guild, user_to_id).await {
+                    error!("{}: {:?}", ERR_ACHIEVEMENTS, why);
+                }
+
                 // Auto moderate the message if necessary
                 let message = add_reaction.message(&ctx.http).await?;
                 auto_moderate(&ctx, &database, guild, message).await?;
@@ -291,6 +514,11 @@ pub async fn reaction_remove(
             let mut member = guild.member(&ctx, user_to_db_id as u64).await?;
             update_roles(&ctx, &database, &mut member).await?;
 
+            // Check the achievements of the user
+            if let Err(why) = check_achievements(ctx, &database, guild, member.user.id).await {
+                error!("{}: {:?}", ERR_ACHIEVEMENTS, why);
+            }
+
             // Auto moderate the message if necessary
             let message = removed_reaction.message(&ctx.http).await?;
             auto_moderate(&ctx, &database, guild, message).await?;
@@ -401,7 +629,8 @@ async fn get_reaction_data(
     Ok((guild_id, user_from_id, user_to_id, channel_id, message_id))
 }
 
-async fn update_roles(
+/// Update the level-up roles of a member according to their current score.
+pub async fn update_roles(
     ctx: &Context,
     database: &Database,
     member: &mut Member,
@@ -419,18 +648,16 @@ async fn update_roles(
     let score = {
         let row = database
             .client
-            .query_one(
+            .query_opt(
                 "
-        SELECT SUM(CASE WHEN upvote THEN 1 ELSE -1 END) score
-        FROM score_reactions r
-        INNER JOIN score_emojis se ON r.guild = se.guild AND r.emoji = se.emoji
-        WHERE r.guild = $1::BIGINT AND user_to = $2::BIGINT
+        SELECT score FROM scores
+        WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
         ",
                 &[&guild_db_id, &user_db_id],
             )
             .await?;
 
-        row.get::<_, Option<i64>>(0).unwrap_or_default()
+        row.map_or(0, |row| row.get::<_, i64>(0))
     };
 
     // Get all roles handled by the level-up system
@@ -448,8 +675,8 @@ async fn update_roles(
             .collect()
     };
 
-    // Get all roles the user should currently have
-    let current: Vec<_> = {
+    // Get all roles the user should currently have and for how long
+    let current: Vec<(RoleId, Option<i64>)> = {
         let rows = database
             .client
             .query(
@@ -463,7 +690,7 @@ async fn update_roles(
                 LIMIT 1
             )
 
-            SELECT role
+            SELECT role, hours
             FROM score_roles
             WHERE guild = $1::BIGINT
                 AND score = (SELECT score FROM role_score)
@@ -473,23 +700,69 @@ async fn update_roles(
             .await?;
 
         rows.iter()
-            .map(|row| RoleId(row.get::<_, i64>(0) as u64))
+            .map(|row| {
+                (
+                    RoleId(row.get::<_, i64>(0) as u64),
+                    row.get::<_, Option<i64>>(1),
+                )
+            })
             .collect()
     };
 
+    // Temporary roles are only granted once per reached level
+    let mut temporary = Vec::new();
+    for (role, hours) in &current {
+        if let Some(hours) = hours {
+            let role_db_id = database.get_role(member.guild_id, *role).await?;
+            let granted = database
+                .client
+                .query_opt(
+                    "
+                SELECT * FROM score_role_grants
+                WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT AND role = $3::BIGINT
+                ",
+                    &[&guild_db_id, &user_db_id, &role_db_id],
+                )
+                .await?
+                .is_some();
+
+            if !granted {
+                temporary.push((*role, *hours));
+            }
+        }
+    }
+
+    // Forget the grants of levels the user is no longer on
+    let current_db_ids: Vec<_> = current.iter().map(|(role, _)| role.0 as i64).collect();
+    database
+        .client
+        .execute(
+            "
+        DELETE FROM score_role_grants
+        WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT AND role != ALL($3::BIGINT[])
+        ",
+            &[&guild_db_id, &user_db_id, &current_db_ids],
+        )
+        .await?;
+
     // Current roles of the user
     let roles = &member.roles;
 
     // Filter roles the user should have but doesn't
     let add: Vec<_> = current
         .iter()
-        .filter(|role| !roles.contains(role))
-        .copied()
+        .filter(|(role, hours)| {
+            !roles.contains(role)
+                && (hours.is_none() || temporary.iter().any(|(temporary, _)| temporary == role))
+        })
+        .map(|(role, _)| *role)
         .collect();
     // Filter roles the user shouldn't have but does
     let remove: Vec<_> = roles
         .iter()
-        .filter(|role| handled.contains(role) && !current.contains(role))
+        .filter(|role| {
+            handled.contains(role) && !current.iter().any(|(current, _)| current == *role)
+        })
         .copied()
         .collect();
 
@@ -502,6 +775,30 @@ async fn update_roles(
         member.remove_roles(&ctx.http, &remove[..]).await?;
     }
 
+    // Schedule the removal of temporary roles
+    for (role, hours) in temporary {
+        let role_db_id = database.get_role(member.guild_id, role).await?;
+        database
+            .client
+            .execute(
+                "
+            INSERT INTO score_role_grants
+            VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT)
+            ON CONFLICT DO NOTHING
+            ",
+                &[&guild_db_id, &user_db_id, &role_db_id],
+            )
+            .await?;
+
+        add_temporary_role(database, member.guild_id, member.user.id, role, hours).await?;
+    }
+    for role in remove {
+        remove_temporary_role(database, member.guild_id, member.user.id, role).await?;
+    }
+
+    // Update the nickname tags
+    update_nicknames(ctx, database, member.guild_id, member.user.id).await?;
+
     Ok(())
 }
 
@@ -13,12 +13,18 @@ use tracing::info;
 #[cfg(feature = "event-calendar")]
 use crate::calendar::host_calendar;
 use crate::{
+    achievements::check_streaks,
     config::Config,
     data,
     database::{client::Database, types::ModuleStatus},
+    event_reminders::check_event_reminders,
+    reaction_slots::check_reaction_slots,
     reminders::check_reminders,
+    scheduled_events::cache_scheduled_events,
     strings::{ERR_CMD_CREATION, ERR_DB_QUERY, INFO_CMD_GLOBAL, INFO_CMD_MODULE, INFO_CONNECTED},
+    temporary_roles::check_temporary_roles,
     utils::{create_command, create_module_command},
+    voice::check_voice,
 };
 
 pub async fn ready(ctx: &Context, rdy: Ready) {
@@ -28,8 +34,29 @@ pub async fn ready(ctx: &Context, rdy: Ready) {
     let activity = Activity::listening("reactions");
     ctx.set_activity(activity).await;
 
-    // Repeatedly check for reminders
-    check_reminders(ctx.clone(), Duration::from_secs(60));
+    // Send reminders when they are due
+    check_reminders(ctx.clone());
+
+    // Bring the cached scheduled events up to date
+    cache_scheduled_events(
+        ctx.clone(),
+        rdy.guilds.iter().map(|guild| guild.id).collect(),
+    );
+
+    // Send event reminders when they are due
+    check_event_reminders(ctx.clone());
+
+    // Repeatedly track the voice activity
+    check_voice(ctx.clone(), Duration::from_secs(60));
+
+    // Repeatedly check for expired temporary roles
+    check_temporary_roles(ctx.clone(), Duration::from_secs(60));
+
+    // Repeatedly check the streaks of rank achievements
+    check_streaks(ctx.clone(), Duration::from_secs(600));
+
+    // Repeatedly reconcile the slots of reaction-roles
+    check_reaction_slots(ctx.clone(), Duration::from_secs(600));
 
     // Activate the event calendar
     #[cfg(feature = "event-calendar")]
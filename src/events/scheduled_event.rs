@@ -0,0 +1,34 @@
+use serenity::{client::Context, json::Value, model::guild::ScheduledEvent};
+
+use crate::{
+    data,
+    database::client::Database,
+    error::KowalskiError,
+    event_reminders::EventReminderScheduler,
+    scheduled_events::{cache_event, uncache_event},
+};
+
+pub async fn scheduled_event(ctx: &Context, name: &str, raw: Value) -> Result<(), KowalskiError> {
+    // Serenity doesn't know about scheduled events yet, so they arrive as unknown events
+    let create_or_update = match name {
+        "GUILD_SCHEDULED_EVENT_CREATE" | "GUILD_SCHEDULED_EVENT_UPDATE" => true,
+        "GUILD_SCHEDULED_EVENT_DELETE" => false,
+        _ => return Ok(()),
+    };
+
+    // Get database and event reminder scheduler
+    let (database, scheduler) = data!(ctx, (Database, EventReminderScheduler));
+
+    let event: ScheduledEvent = serde_json::from_value(raw)?;
+
+    if create_or_update {
+        cache_event(&database, &event).await?;
+    } else {
+        uncache_event(&database, event.guild_id, event.id).await?;
+    }
+
+    // The start of the event might have moved
+    scheduler.reschedule();
+
+    Ok(())
+}
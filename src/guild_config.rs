@@ -0,0 +1,1080 @@
+use serde::{Deserialize, Serialize};
+use serenity::{
+    client::Context,
+    model::{
+        channel::{Channel, ReactionType},
+        guild::Guild,
+        id::{ChannelId, EmojiId, GuildId, MessageId, RoleId},
+    },
+};
+
+use crate::{
+    database::client::Database,
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    reaction_messages::{create_reaction_message, update_reaction_message, MessageRole},
+    reaction_slots::reconcile_slots,
+    strings::ERR_GUILD_NOT_CACHED,
+};
+
+/// The module configuration of a guild, referring to roles, channels and emojis by name.
+#[derive(Serialize, Deserialize, Default)]
+pub struct GuildConfig {
+    #[serde(default)]
+    pub level_ups: Vec<LevelUp>,
+    #[serde(default)]
+    pub emojis: Vec<ScoreEmoji>,
+    #[serde(default)]
+    pub cooldowns: Vec<Cooldown>,
+    #[serde(default)]
+    pub moderation: Moderation,
+    #[serde(default)]
+    pub reaction_messages: Vec<ReactionMessage>,
+    #[serde(default)]
+    pub reaction_roles: Vec<MessageReactionRoles>,
+    #[serde(default)]
+    pub requirements: Vec<Requirement>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct LevelUp {
+    pub role: String,
+    pub score: i64,
+    pub hours: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ScoreEmoji {
+    pub emoji: String,
+    pub upvote: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Cooldown {
+    pub role: String,
+    pub cooldown: i64,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct Moderation {
+    pub auto_pin: Option<i64>,
+    pub auto_delete: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReactionMessage {
+    pub channel: String,
+    pub title: String,
+    pub description: String,
+    pub mode: Option<String>,
+    pub maximum: Option<i32>,
+    pub roles: Vec<ReactionRole>,
+}
+
+/// Reaction-roles of a message not composed by the bot, which can only be imported on the guild
+/// the message belongs to.
+#[derive(Serialize, Deserialize)]
+pub struct MessageReactionRoles {
+    pub channel: String,
+    pub message: u64,
+    pub mode: Option<String>,
+    pub maximum: Option<i32>,
+    pub roles: Vec<ReactionRole>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ReactionRole {
+    pub emoji: String,
+    pub role: String,
+    pub slots: Option<i32>,
+    pub hours: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Requirement {
+    pub role: String,
+    pub requirement: String,
+    pub forbidden: bool,
+}
+
+/// Export the module configuration of a guild.
+pub async fn export_config(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+) -> Result<GuildConfig, KowalskiError> {
+    let guild = get_guild(ctx, guild_id)?;
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    let mut config = GuildConfig::default();
+
+    // Get level-up roles
+    for row in database
+        .client
+        .query(
+            "SELECT role, score, hours FROM score_roles WHERE guild = $1::BIGINT ORDER BY score",
+            &[&guild_db_id],
+        )
+        .await?
+    {
+        if let Some(role) = role_name(&guild, row.get(0)) {
+            config.level_ups.push(LevelUp {
+                role,
+                score: row.get(1),
+                hours: row.get(2),
+            });
+        }
+    }
+
+    // Get up- and downvote emojis
+    for row in database
+        .client
+        .query(
+            "
+            SELECT unicode, guild_emoji, upvote FROM score_emojis se
+            INNER JOIN emojis e ON se.emoji = e.id
+            WHERE se.guild = $1::BIGINT
+            ORDER BY upvote DESC, emoji
+            ",
+            &[&guild_db_id],
+        )
+        .await?
+    {
+        if let Some(emoji) = emoji_name(&guild, row.get(0), row.get(1)) {
+            config.emojis.push(ScoreEmoji {
+                emoji,
+                upvote: row.get(2),
+            });
+        }
+    }
+
+    // Get role cooldowns
+    for row in database
+        .client
+        .query(
+            "SELECT role, cooldown FROM score_cooldowns WHERE guild = $1::BIGINT ORDER BY role",
+            &[&guild_db_id],
+        )
+        .await?
+    {
+        if let Some(role) = role_name(&guild, row.get(0)) {
+            config.cooldowns.push(Cooldown {
+                role,
+                cooldown: row.get(1),
+            });
+        }
+    }
+
+    // Get auto-moderation scores
+    config.moderation = Moderation {
+        auto_pin: database
+            .client
+            .query_opt(
+                "SELECT score FROM score_auto_pin WHERE guild = $1::BIGINT",
+                &[&guild_db_id],
+            )
+            .await?
+            .map(|row| row.get(0)),
+        auto_delete: database
+            .client
+            .query_opt(
+                "SELECT score FROM score_auto_delete WHERE guild = $1::BIGINT",
+                &[&guild_db_id],
+            )
+            .await?
+            .map(|row| row.get(0)),
+    };
+
+    // Get the reaction-role messages composed by the bot
+    for message in database
+        .client
+        .query(
+            "
+            SELECT m.channel, m.message, title, description, mode, maximum
+            FROM reaction_role_messages m
+            LEFT JOIN reaction_role_groups g
+                ON m.guild = g.guild AND m.channel = g.channel AND m.message = g.message
+            WHERE m.guild = $1::BIGINT
+            ORDER BY m.channel, m.message
+            ",
+            &[&guild_db_id],
+        )
+        .await?
+    {
+        let channel = match channel_name(&guild, message.get(0)) {
+            Some(channel) => channel,
+            None => continue,
+        };
+
+        let roles = export_reaction_roles(
+            database,
+            &guild,
+            guild_db_id,
+            message.get(0),
+            message.get(1),
+        )
+        .await?;
+
+        config.reaction_messages.push(ReactionMessage {
+            channel,
+            title: message.get(2),
+            description: message.get(3),
+            mode: message.get(4),
+            maximum: message.get(5),
+            roles,
+        });
+    }
+
+    // Get the reaction-roles of messages not composed by the bot
+    for message in database
+        .client
+        .query(
+            "
+            SELECT DISTINCT rr.channel, rr.message, mode, maximum
+            FROM reaction_roles rr
+            LEFT JOIN reaction_role_groups g
+                ON rr.guild = g.guild AND rr.channel = g.channel AND rr.message = g.message
+            WHERE rr.guild = $1::BIGINT AND NOT EXISTS (
+                SELECT * FROM reaction_role_messages m
+                WHERE m.guild = rr.guild AND m.channel = rr.channel AND m.message = rr.message
+            )
+            ORDER BY rr.channel, rr.message
+            ",
+            &[&guild_db_id],
+        )
+        .await?
+    {
+        let channel = match channel_name(&guild, message.get(0)) {
+            Some(channel) => channel,
+            None => continue,
+        };
+
+        let roles = export_reaction_roles(
+            database,
+            &guild,
+            guild_db_id,
+            message.get(0),
+            message.get(1),
+        )
+        .await?;
+
+        config.reaction_roles.push(MessageReactionRoles {
+            channel,
+            message: message.get::<_, i64>(1) as u64,
+            mode: message.get(2),
+            maximum: message.get(3),
+            roles,
+        });
+    }
+
+    // Get the requirements of the reaction-roles
+    for row in database
+        .client
+        .query(
+            "
+            SELECT role, requirement, forbidden FROM reaction_role_requirements
+            WHERE guild = $1::BIGINT
+            ORDER BY role, requirement
+            ",
+            &[&guild_db_id],
+        )
+        .await?
+    {
+        if let (Some(role), Some(requirement)) =
+            (role_name(&guild, row.get(0)), role_name(&guild, row.get(1)))
+        {
+            config.requirements.push(Requirement {
+                role,
+                requirement,
+                forbidden: row.get(2),
+            });
+        }
+    }
+
+    Ok(config)
+}
+
+// Get the reaction-roles of a message, referring to roles and emojis by name
+async fn export_reaction_roles(
+    database: &Database,
+    guild: &Guild,
+    guild_db_id: i64,
+    channel_db_id: i64,
+    message_db_id: i64,
+) -> Result<Vec<ReactionRole>, KowalskiError> {
+    let mut roles = Vec::new();
+
+    for row in database
+        .client
+        .query(
+            "
+            SELECT unicode, guild_emoji, role, capacity, hours
+            FROM reaction_roles rr
+            INNER JOIN emojis e ON rr.emoji = e.id
+            WHERE rr.guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+            ORDER BY rr.emoji, role
+            ",
+            &[&guild_db_id, &channel_db_id, &message_db_id],
+        )
+        .await?
+    {
+        if let (Some(emoji), Some(role)) = (
+            emoji_name(guild, row.get(0), row.get(1)),
+            role_name(guild, row.get(2)),
+        ) {
+            roles.push(ReactionRole {
+                emoji,
+                role,
+                slots: row.get(3),
+                hours: row.get(4),
+            });
+        }
+    }
+
+    Ok(roles)
+}
+
+/// A configuration mapped onto the roles, channels and emojis of a guild.
+pub struct Import {
+    /// Human-readable description of the changes.
+    pub changes: Vec<String>,
+    /// Entries which couldn't be mapped onto the guild.
+    pub skipped: Vec<String>,
+    level_ups: Vec<(RoleId, i64, Option<i64>)>,
+    emojis: Vec<(ReactionType, bool)>,
+    cooldowns: Vec<(RoleId, i64)>,
+    moderation: (Option<i64>, Option<i64>),
+    reaction_messages: Vec<PlannedMessage>,
+    requirements: Vec<(RoleId, RoleId, bool)>,
+}
+
+// Reaction-roles to attach to a message
+struct PlannedMessage {
+    channel: ChannelId,
+    // Existing message to attach the reaction-roles to, otherwise a new message gets composed
+    message: Option<MessageId>,
+    title: String,
+    description: String,
+    mode: Option<String>,
+    maximum: Option<i32>,
+    roles: Vec<MessageRole>,
+}
+
+/// Map a configuration onto a guild and describe the changes importing it would make.
+pub async fn plan_import(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    config: GuildConfig,
+) -> Result<Import, KowalskiError> {
+    let guild = get_guild(ctx, guild_id)?;
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    let mut import = Import {
+        changes: Vec::new(),
+        skipped: Vec::new(),
+        level_ups: Vec::new(),
+        emojis: Vec::new(),
+        cooldowns: Vec::new(),
+        moderation: (None, None),
+        reaction_messages: Vec::new(),
+        requirements: Vec::new(),
+    };
+
+    for level_up in config.level_ups {
+        let role = match find_role(&guild, &level_up.role) {
+            Some(role) => role,
+            None => {
+                import
+                    .skipped
+                    .push(format!("Level-up role: unknown role {}", level_up.role));
+                continue;
+            }
+        };
+
+        let current = database
+            .client
+            .query_opt(
+                "
+                SELECT hours FROM score_roles
+                WHERE guild = $1::BIGINT AND role = $2::BIGINT AND score = $3::BIGINT
+                ",
+                &[&guild_db_id, &(role.0 as i64), &level_up.score],
+            )
+            .await?
+            .map(|row| row.get::<_, Option<i64>>(0));
+
+        import.changes.push(format!(
+            "{} Level-up role {} on score {}",
+            marker(current.map(|hours| hours == level_up.hours)),
+            level_up.role,
+            level_up.score
+        ));
+        import
+            .level_ups
+            .push((role, level_up.score, level_up.hours));
+    }
+
+    for emoji in config.emojis {
+        let reaction = match find_emoji(&guild, &emoji.emoji) {
+            Some(reaction) => reaction,
+            None => {
+                import
+                    .skipped
+                    .push(format!("Emoji: unknown emoji {}", emoji.emoji));
+                continue;
+            }
+        };
+
+        let current = database
+            .client
+            .query_opt(
+                "
+                SELECT upvote FROM score_emojis se
+                INNER JOIN emojis e ON se.emoji = e.id
+                WHERE se.guild = $1::BIGINT
+                AND (unicode = $2::TEXT OR guild_emoji = $3::BIGINT)
+                ",
+                &[&guild_db_id, &unicode(&reaction), &custom_id(&reaction)],
+            )
+            .await?
+            .map(|row| row.get::<_, bool>(0));
+
+        import.changes.push(format!(
+            "{} {} emoji {}",
+            marker(current.map(|upvote| upvote == emoji.upvote)),
+            if emoji.upvote { "Upvote" } else { "Downvote" },
+            emoji.emoji
+        ));
+        import.emojis.push((reaction, emoji.upvote));
+    }
+
+    for cooldown in config.cooldowns {
+        let role = match find_role(&guild, &cooldown.role) {
+            Some(role) => role,
+            None => {
+                import
+                    .skipped
+                    .push(format!("Cooldown: unknown role {}", cooldown.role));
+                continue;
+            }
+        };
+
+        let current = database
+            .client
+            .query_opt(
+                "
+                SELECT cooldown FROM score_cooldowns
+                WHERE guild = $1::BIGINT AND role = $2::BIGINT
+                ",
+                &[&guild_db_id, &(role.0 as i64)],
+            )
+            .await?
+            .map(|row| row.get::<_, i64>(0));
+
+        import.changes.push(format!(
+            "{} Cooldown of {} seconds for {}",
+            marker(current.map(|current| current == cooldown.cooldown)),
+            cooldown.cooldown,
+            cooldown.role
+        ));
+        import.cooldowns.push((role, cooldown.cooldown));
+    }
+
+    for (table, name, score) in [
+        ("score_auto_pin", "Auto-pin", config.moderation.auto_pin),
+        (
+            "score_auto_delete",
+            "Auto-delete",
+            config.moderation.auto_delete,
+        ),
+    ] {
+        if let Some(score) = score {
+            let current = database
+                .client
+                .query_opt(
+                    &format!("SELECT score FROM {} WHERE guild = $1::BIGINT", table),
+                    &[&guild_db_id],
+                )
+                .await?
+                .map(|row| row.get::<_, i64>(0));
+
+            import.changes.push(format!(
+                "{} {} on score {}",
+                marker(current.map(|current| current == score)),
+                name,
+                score
+            ));
+        }
+    }
+    import.moderation = (config.moderation.auto_pin, config.moderation.auto_delete);
+
+    for message in config.reaction_messages {
+        let channel = match find_channel(&guild, &message.channel) {
+            Some(channel) => channel,
+            None => {
+                import.skipped.push(format!(
+                    "Reaction-role message {}: unknown channel {}",
+                    message.title, message.channel
+                ));
+                continue;
+            }
+        };
+
+        let roles = map_roles(&guild, &message.roles, &mut import.skipped);
+        if roles.is_empty() {
+            import.skipped.push(format!(
+                "Reaction-role message {}: no roles left",
+                message.title
+            ));
+            continue;
+        }
+
+        // Update a message composed earlier instead of posting it again
+        let existing = database
+            .client
+            .query_opt(
+                "
+                SELECT message FROM reaction_role_messages
+                WHERE guild = $1::BIGINT AND channel = $2::BIGINT
+                AND title = $3::TEXT AND description = $4::TEXT
+                LIMIT 1
+                ",
+                &[
+                    &guild_db_id,
+                    &(channel.0 as i64),
+                    &message.title,
+                    &message.description,
+                ],
+            )
+            .await?
+            .map(|row| MessageId(row.get::<_, i64>(0) as u64));
+
+        let planned = PlannedMessage {
+            channel,
+            message: existing,
+            title: message.title,
+            description: message.description,
+            mode: message.mode,
+            maximum: message.maximum,
+            roles,
+        };
+
+        let unchanged = match existing {
+            Some(existing) => Some(is_unchanged(database, guild_db_id, existing, &planned).await?),
+            None => None,
+        };
+
+        import.changes.push(format!(
+            "{} Reaction-role message {} in #{} with {} roles",
+            marker(unchanged),
+            planned.title,
+            message.channel,
+            planned.roles.len()
+        ));
+        if unchanged != Some(true) {
+            import.reaction_messages.push(planned);
+        }
+    }
+
+    for message in config.reaction_roles {
+        let channel = match find_channel(&guild, &message.channel) {
+            Some(channel) => channel,
+            None => {
+                import.skipped.push(format!(
+                    "Reaction-roles of message {}: unknown channel {}",
+                    message.message, message.channel
+                ));
+                continue;
+            }
+        };
+
+        // The message has to exist on this guild
+        let message_id = MessageId(message.message);
+        if channel.message(&ctx.http, message_id).await.is_err() {
+            import.skipped.push(format!(
+                "Reaction-roles of message {}: unknown message in #{}",
+                message.message, message.channel
+            ));
+            continue;
+        }
+
+        let roles = map_roles(&guild, &message.roles, &mut import.skipped);
+        if roles.is_empty() {
+            continue;
+        }
+
+        let planned = PlannedMessage {
+            channel,
+            message: Some(message_id),
+            title: String::new(),
+            description: String::new(),
+            mode: message.mode,
+            maximum: message.maximum,
+            roles,
+        };
+
+        let unchanged = is_unchanged(database, guild_db_id, message_id, &planned).await?;
+
+        import.changes.push(format!(
+            "{} Reaction-roles of message {} in #{} with {} roles",
+            marker(Some(unchanged)),
+            message.message,
+            message.channel,
+            planned.roles.len()
+        ));
+        if !unchanged {
+            import.reaction_messages.push(planned);
+        }
+    }
+
+    for requirement in config.requirements {
+        let (role, requirement_role) = match (
+            find_role(&guild, &requirement.role),
+            find_role(&guild, &requirement.requirement),
+        ) {
+            (Some(role), Some(requirement_role)) => (role, requirement_role),
+            _ => {
+                import.skipped.push(format!(
+                    "Requirement of {}: unknown role {} or {}",
+                    requirement.role, requirement.role, requirement.requirement
+                ));
+                continue;
+            }
+        };
+
+        let current = database
+            .client
+            .query_opt(
+                "
+                SELECT forbidden FROM reaction_role_requirements
+                WHERE guild = $1::BIGINT AND role = $2::BIGINT AND requirement = $3::BIGINT
+                ",
+                &[&guild_db_id, &(role.0 as i64), &(requirement_role.0 as i64)],
+            )
+            .await?
+            .map(|row| row.get::<_, bool>(0));
+
+        import.changes.push(format!(
+            "{} {} {} {}",
+            marker(current.map(|forbidden| forbidden == requirement.forbidden)),
+            requirement.role,
+            if requirement.forbidden {
+                "can't be picked with"
+            } else {
+                "requires"
+            },
+            requirement.requirement
+        ));
+        import
+            .requirements
+            .push((role, requirement_role, requirement.forbidden));
+    }
+
+    Ok(import)
+}
+
+/// Apply a planned import to a guild.
+///
+/// Note: Existing entries which are not part of the import are kept.
+pub async fn apply_import(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    import: Import,
+) -> Result<(), KowalskiError> {
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    for (role, score, hours) in import.level_ups {
+        let role_db_id = database.get_role(guild_id, role).await?;
+
+        database
+            .client
+            .execute(
+                "
+                INSERT INTO score_roles
+                VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::BIGINT)
+                ON CONFLICT (guild, role, score)
+                DO UPDATE SET hours = $4::BIGINT
+                ",
+                &[&guild_db_id, &role_db_id, &score, &hours],
+            )
+            .await?;
+    }
+
+    for (emoji, upvote) in import.emojis {
+        let emoji_db_id = database.get_emoji(guild_id, &emoji).await?;
+
+        database
+            .client
+            .execute(
+                "
+                INSERT INTO score_emojis
+                VALUES ($1::BIGINT, $2::INT, $3::BOOLEAN)
+                ON CONFLICT (guild, emoji)
+                DO UPDATE SET upvote = $3::BOOLEAN
+                ",
+                &[&guild_db_id, &emoji_db_id, &upvote],
+            )
+            .await?;
+    }
+
+    for (role, cooldown) in import.cooldowns {
+        let role_db_id = database.get_role(guild_id, role).await?;
+
+        database
+            .client
+            .execute(
+                "
+                INSERT INTO score_cooldowns
+                VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT)
+                ON CONFLICT (guild, role)
+                DO UPDATE SET cooldown = $3::BIGINT
+                ",
+                &[&guild_db_id, &role_db_id, &cooldown],
+            )
+            .await?;
+    }
+
+    let (auto_pin, auto_delete) = import.moderation;
+    for (table, score) in [
+        ("score_auto_pin", auto_pin),
+        ("score_auto_delete", auto_delete),
+    ] {
+        if let Some(score) = score {
+            database
+                .client
+                .execute(
+                    &format!(
+                        "
+                        INSERT INTO {}
+                        VALUES ($1::BIGINT, $2::BIGINT)
+                        ON CONFLICT (guild) DO UPDATE SET score = $2::BIGINT
+                        ",
+                        table
+                    ),
+                    &[&guild_db_id, &score],
+                )
+                .await?;
+        }
+    }
+
+    let mut limited = false;
+
+    for message in import.reaction_messages {
+        limited |= message.roles.iter().any(|role| role.slots.is_some());
+
+        let message_id = match message.message {
+            Some(message_id) => {
+                attach_roles(
+                    ctx,
+                    database,
+                    guild_id,
+                    message.channel,
+                    message_id,
+                    &message.roles,
+                )
+                .await?;
+
+                message_id
+            }
+            None => match create_reaction_message(
+                ctx,
+                database,
+                guild_id,
+                message.channel,
+                &message.title,
+                &message.description,
+                &message.roles,
+            )
+            .await?
+            {
+                Some(posted) => posted.id,
+                None => continue,
+            },
+        };
+
+        // Group the reaction-roles of the message again
+        if let Some(mode) = message.mode {
+            let channel_db_id = database.get_channel(guild_id, message.channel).await?;
+            let message_db_id = database
+                .get_message(guild_id, message.channel, message_id)
+                .await?;
+
+            database
+                .client
+                .execute(
+                    "
+                    INSERT INTO reaction_role_groups
+                    VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::TEXT, $5::INT)
+                    ON CONFLICT (guild, channel, message)
+                    DO UPDATE SET mode = $4::TEXT, maximum = $5::INT
+                    ",
+                    &[
+                        &guild_db_id,
+                        &channel_db_id,
+                        &message_db_id,
+                        &mode,
+                        &message.maximum,
+                    ],
+                )
+                .await?;
+        }
+    }
+
+    // Account for the members already holding the limited roles
+    if limited {
+        reconcile_slots(ctx, database, guild_id).await?;
+    }
+
+    for (role, requirement, forbidden) in import.requirements {
+        let role_db_id = database.get_role(guild_id, role).await?;
+        let requirement_db_id = database.get_role(guild_id, requirement).await?;
+
+        database
+            .client
+            .execute(
+                "
+                INSERT INTO reaction_role_requirements
+                VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::BOOLEAN)
+                ON CONFLICT (guild, role, requirement)
+                DO UPDATE SET forbidden = $4::BOOLEAN
+                ",
+                &[&guild_db_id, &role_db_id, &requirement_db_id, &forbidden],
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+// Add reaction-roles to an existing message, updating the ones it already has
+async fn attach_roles(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    roles: &[MessageRole],
+) -> Result<(), KowalskiError> {
+    // Get guild, channel and message ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let channel_db_id = database.get_channel(guild_id, channel_id).await?;
+    let message_db_id = database
+        .get_message(guild_id, channel_id, message_id)
+        .await?;
+
+    for role in roles {
+        let emoji_db_id = database.get_emoji(guild_id, &role.emoji).await?;
+        let role_db_id = database.get_role(guild_id, role.role).await?;
+
+        database
+            .client
+            .execute(
+                "
+                INSERT INTO reaction_roles
+                VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::INT, $5::BIGINT,
+                    $6::INT, $7::BIGINT, $6::INT)
+                ON CONFLICT (guild, channel, message, emoji, role)
+                DO UPDATE SET slots = $6::INT, hours = $7::BIGINT, capacity = $6::INT
+                ",
+                &[
+                    &guild_db_id,
+                    &channel_db_id,
+                    &message_db_id,
+                    &emoji_db_id,
+                    &role_db_id,
+                    &role.slots,
+                    &role.hours,
+                ],
+            )
+            .await?;
+
+        channel_id
+            .create_reaction(&ctx.http, message_id, role.emoji.clone())
+            .await?;
+    }
+
+    // Re-render messages composed by the bot
+    update_reaction_message(ctx, database, guild_id, channel_id, message_id).await
+}
+
+// Check whether a message already has all of the planned reaction-roles and grouping
+async fn is_unchanged(
+    database: &Database,
+    guild_db_id: i64,
+    message_id: MessageId,
+    message: &PlannedMessage,
+) -> Result<bool, KowalskiError> {
+    let channel_db_id = message.channel.0 as i64;
+    let message_db_id = message_id.0 as i64;
+
+    let current: Vec<_> = database
+        .client
+        .query(
+            "
+            SELECT unicode, guild_emoji, role, capacity, hours
+            FROM reaction_roles rr
+            INNER JOIN emojis e ON rr.emoji = e.id
+            WHERE rr.guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+            ",
+            &[&guild_db_id, &channel_db_id, &message_db_id],
+        )
+        .await?
+        .iter()
+        .map(|row| {
+            (
+                row.get::<_, Option<String>>(0),
+                row.get::<_, Option<i64>>(1),
+                row.get::<_, i64>(2),
+                row.get::<_, Option<i32>>(3),
+                row.get::<_, Option<i64>>(4),
+            )
+        })
+        .collect();
+
+    let roles_unchanged = message.roles.iter().all(|role| {
+        current.contains(&(
+            unicode(&role.emoji),
+            custom_id(&role.emoji),
+            role.role.0 as i64,
+            role.slots,
+            role.hours,
+        ))
+    });
+
+    let group_unchanged = match &message.mode {
+        Some(mode) => database
+            .client
+            .query_opt(
+                "
+                SELECT mode, maximum FROM reaction_role_groups
+                WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                ",
+                &[&guild_db_id, &channel_db_id, &message_db_id],
+            )
+            .await?
+            .is_some_and(|row| {
+                row.get::<_, String>(0) == *mode && row.get::<_, Option<i32>>(1) == message.maximum
+            }),
+        None => true,
+    };
+
+    Ok(roles_unchanged && group_unchanged)
+}
+
+// Map exported reaction-roles onto the emojis and roles of a guild
+fn map_roles(guild: &Guild, roles: &[ReactionRole], skipped: &mut Vec<String>) -> Vec<MessageRole> {
+    let mut mapped = Vec::new();
+
+    for role in roles {
+        match (find_emoji(guild, &role.emoji), find_role(guild, &role.role)) {
+            (Some(emoji), Some(role_id)) => mapped.push(MessageRole {
+                emoji,
+                role: role_id,
+                slots: role.slots,
+                hours: role.hours,
+            }),
+            _ => skipped.push(format!(
+                "Reaction-role {} {}: unknown emoji or role",
+                role.emoji, role.role
+            )),
+        }
+    }
+
+    mapped
+}
+
+fn get_guild(ctx: &Context, guild_id: GuildId) -> Result<Guild, KowalskiError> {
+    guild_id
+        .to_guild_cached(ctx)
+        .ok_or_else(|| DiscordApiError(ERR_GUILD_NOT_CACHED.to_string()))
+}
+
+// Describe whether an entry is new (+), changed (~) or unchanged (=)
+fn marker(unchanged: Option<bool>) -> &'static str {
+    match unchanged {
+        None => "+",
+        Some(false) => "~",
+        Some(true) => "=",
+    }
+}
+
+fn role_name(guild: &Guild, role_db_id: i64) -> Option<String> {
+    guild
+        .roles
+        .get(&RoleId(role_db_id as u64))
+        .map(|role| role.name.clone())
+}
+
+fn channel_name(guild: &Guild, channel_db_id: i64) -> Option<String> {
+    match guild.channels.get(&ChannelId(channel_db_id as u64)) {
+        Some(Channel::Guild(channel)) => Some(channel.name.clone()),
+        _ => None,
+    }
+}
+
+fn emoji_name(guild: &Guild, unicode: Option<String>, guild_emoji: Option<i64>) -> Option<String> {
+    match (unicode, guild_emoji) {
+        (Some(string), _) => Some(string),
+        (_, Some(id)) => guild
+            .emojis
+            .get(&EmojiId(id as u64))
+            .map(|emoji| format!(":{}:", emoji.name)),
+        _ => None,
+    }
+}
+
+fn find_role(guild: &Guild, name: &str) -> Option<RoleId> {
+    guild
+        .roles
+        .values()
+        .find(|role| role.name == name)
+        .map(|role| role.id)
+}
+
+fn find_channel(guild: &Guild, name: &str) -> Option<ChannelId> {
+    guild.channels.values().find_map(|channel| match channel {
+        Channel::Guild(channel) if channel.name == name => Some(channel.id),
+        _ => None,
+    })
+}
+
+fn find_emoji(guild: &Guild, name: &str) -> Option<ReactionType> {
+    // Custom emojis are exported as :name:
+    match name
+        .strip_prefix(':')
+        .and_then(|name| name.strip_suffix(':'))
+    {
+        Some(name) if !name.is_empty() => guild
+            .emojis
+            .values()
+            .find(|emoji| emoji.name == name)
+            .map(|emoji| ReactionType::Custom {
+                animated: emoji.animated,
+                id: emoji.id,
+                name: Some(emoji.name.clone()),
+            }),
+        _ if !name.is_empty() => Some(ReactionType::Unicode(name.to_string())),
+        _ => None,
+    }
+}
+
+fn unicode(emoji: &ReactionType) -> Option<String> {
+    match emoji {
+        ReactionType::Unicode(string) => Some(string.clone()),
+        _ => None,
+    }
+}
+
+fn custom_id(emoji: &ReactionType) -> Option<i64> {
+    match emoji {
+        ReactionType::Custom { id, .. } => Some(id.0 as i64),
+        _ => None,
+    }
+}
@@ -1,3 +1,4 @@
+pub mod achievements;
 #[cfg(feature = "event-calendar")]
 pub mod calendar;
 pub mod client;
@@ -7,10 +8,22 @@ pub mod cooldowns;
 pub mod credits;
 pub mod database;
 pub mod error;
+pub mod event_reminders;
 pub mod events;
+pub mod guild_config;
 pub mod history;
 #[cfg(feature = "nlp-model")]
 pub mod model;
+pub mod nicknames;
+pub mod reaction_messages;
+pub mod reaction_slots;
+pub mod recurrence;
 pub mod reminders;
+pub mod role_menus;
+pub mod role_requirements;
+pub mod scheduled_events;
 pub mod strings;
+pub mod temporary_roles;
+pub mod time_parser;
 pub mod utils;
+pub mod voice;
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+
+use serenity::{
+    client::Context,
+    model::id::{GuildId, RoleId, UserId},
+};
+
+use crate::{config::Config, data, database::client::Database, error::KowalskiError};
+
+/// Maximum length of a nickname allowed by Discord.
+const NICKNAME_LENGTH: usize = 32;
+
+/// Update the nickname tags of a member and of all users whose tag might have changed with it.
+pub async fn update_nicknames(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    user_id: UserId,
+) -> Result<(), KowalskiError> {
+    // Get config
+    let config = data!(ctx, Config);
+
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    // Get the nickname settings of the guild
+    let settings = database
+        .client
+        .query_opt(
+            "
+            SELECT source, suffix, ranking FROM score_nicknames
+            WHERE guild = $1::BIGINT
+            ",
+            &[&guild_db_id],
+        )
+        .await?
+        .map(|row| {
+            (
+                row.get::<_, String>(0),
+                row.get::<_, bool>(1),
+                row.get::<_, Vec<i64>>(2),
+            )
+        });
+
+    let (source, suffix, ranking) = match settings {
+        Some(settings) => settings,
+        None => return Ok(()),
+    };
+
+    let mut tags = HashMap::new();
+    let mut applied_ranking = None;
+
+    if source == "rank" {
+        let titles = &config.general.leaderboard_titles;
+
+        let new_ranking: Vec<i64> = database
+            .client
+            .query(
+                "
+                SELECT \"user\" FROM scores
+                WHERE guild = $1::BIGINT
+                ORDER BY score DESC, \"user\"
+                LIMIT $2::BIGINT
+                ",
+                &[&guild_db_id, &(titles.len() as i64)],
+            )
+            .await?
+            .iter()
+            .map(|row| row.get(0))
+            .collect();
+
+        // Only the users whose position on the leaderboard changed have to be renamed
+        if new_ranking == ranking {
+            return Ok(());
+        }
+
+        for user in ranking.iter().chain(&new_ranking) {
+            let old_title = ranking.iter().position(|other| other == user);
+            let new_title = new_ranking.iter().position(|other| other == user);
+
+            if old_title != new_title {
+                tags.insert(
+                    UserId(*user as u64),
+                    new_title.map(|index| titles[index].clone()),
+                );
+            }
+        }
+
+        applied_ranking = Some(new_ranking);
+    } else {
+        // Get the user id
+        let user_db_id = database.get_user(guild_id, user_id).await?;
+
+        // Use the name of the highest level-up role as the tier of the user
+        let role = database
+            .client
+            .query_opt(
+                "
+                WITH score AS (
+                    SELECT score FROM scores
+                    WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
+                )
+
+                SELECT role
+                FROM score_roles
+                WHERE guild = $1::BIGINT
+                    AND ((score >= 0 AND score <= COALESCE((SELECT score FROM score), 0))
+                    OR (score < 0 AND score >= COALESCE((SELECT score FROM score), 0)))
+                ORDER BY ABS(score) DESC
+                LIMIT 1
+                ",
+                &[&guild_db_id, &user_db_id],
+            )
+            .await?
+            .map(|row| RoleId(row.get::<_, i64>(0) as u64));
+
+        let title = role.and_then(|role| role.to_role_cached(ctx).map(|role| role.name));
+
+        tags.insert(user_id, title);
+    }
+
+    for (user_id, title) in tags {
+        set_tag(ctx, database, guild_id, user_id, title, suffix).await?;
+    }
+
+    // Remember the ranking the tags are based on
+    if let Some(new_ranking) = applied_ranking {
+        database
+            .client
+            .execute(
+                "
+                UPDATE score_nicknames SET ranking = $2::BIGINT[]
+                WHERE guild = $1::BIGINT
+                ",
+                &[&guild_db_id, &new_ranking],
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Restore the original nicknames of all tagged members of a guild.
+pub async fn restore_nicknames(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+) -> Result<(), KowalskiError> {
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    let rows = database
+        .client
+        .query(
+            "
+            SELECT \"user\" FROM score_nickname_originals
+            WHERE guild = $1::BIGINT
+            ",
+            &[&guild_db_id],
+        )
+        .await?;
+
+    for row in rows {
+        let user_id = UserId(row.get::<_, i64>(0) as u64);
+
+        set_tag(ctx, database, guild_id, user_id, None, false).await?;
+    }
+
+    Ok(())
+}
+
+/// Tag the nickname of a member with the given title or restore it if no title is given.
+async fn set_tag(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    user_id: UserId,
+    title: Option<String>,
+    suffix: bool,
+) -> Result<(), KowalskiError> {
+    // Get guild and user ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let user_db_id = database.get_user(guild_id, user_id).await?;
+
+    // Get the original nickname of the member if it is currently tagged
+    let original = database
+        .client
+        .query_opt(
+            "
+            SELECT nickname FROM score_nickname_originals
+            WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
+            ",
+            &[&guild_db_id, &user_db_id],
+        )
+        .await?
+        .map(|row| row.get::<_, Option<String>>(0));
+
+    // Nothing to do for untagged members without a title
+    if title.is_none() && original.is_none() {
+        return Ok(());
+    }
+
+    let member = match guild_id.member(&ctx, user_id).await {
+        Ok(member) if !member.user.bot => member,
+        _ => return Ok(()),
+    };
+
+    // Members above the bot (including the owner) can't be renamed
+    let renamable = guild_id.to_guild_cached(ctx).is_some_and(|guild| {
+        let bot_id = ctx.cache.current_user_id();
+        guild.greater_member_hierarchy(ctx, bot_id, user_id) == Some(bot_id)
+    });
+
+    if !renamable {
+        return Ok(());
+    }
+
+    let nickname = match (&title, &original) {
+        (Some(title), original) => {
+            let base = match original {
+                Some(original) => original.clone(),
+                None => member.nick.clone(),
+            }
+            .unwrap_or_else(|| member.user.name.clone());
+
+            // Shorten the name to fit the tag into the nickname
+            let tag = format!("[{}]", title);
+            let length = NICKNAME_LENGTH.saturating_sub(tag.chars().count() + 1);
+            let base: String = base.chars().take(length).collect();
+
+            if suffix {
+                format!("{} {}", base, tag)
+            } else {
+                format!("{} {}", tag, base)
+            }
+        }
+        // An empty nickname resets it to the username
+        (None, original) => original.clone().flatten().unwrap_or_default(),
+    };
+
+    if member.nick.clone().unwrap_or_default() != nickname {
+        let edited = guild_id
+            .edit_member(&ctx.http, user_id, |member| member.nickname(&nickname))
+            .await;
+
+        // Missing permissions shouldn't prevent the score from being updated
+        if edited.is_err() {
+            return Ok(());
+        }
+    }
+
+    if title.is_some() {
+        if original.is_none() {
+            database
+                .client
+                .execute(
+                    "
+                    INSERT INTO score_nickname_originals
+                    VALUES ($1::BIGINT, $2::BIGINT, $3::TEXT)
+                    ON CONFLICT
+                    DO NOTHING
+                    ",
+                    &[&guild_db_id, &user_db_id, &member.nick],
+                )
+                .await?;
+        }
+    } else {
+        database
+            .client
+            .execute(
+                "
+                DELETE FROM score_nickname_originals
+                WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT
+                ",
+                &[&guild_db_id, &user_db_id],
+            )
+            .await?;
+    }
+
+    Ok(())
+}
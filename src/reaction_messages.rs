@@ -0,0 +1,193 @@
+use itertools::Itertools;
+use serenity::{
+    client::Context,
+    model::{
+        channel::{Message, ReactionType},
+        id::{ChannelId, EmojiId, GuildId, MessageId, RoleId},
+    },
+    prelude::Mentionable,
+};
+
+use crate::{database::client::Database, error::KowalskiError, utils::create_embed};
+
+/// A reaction-role of a message composed by the bot.
+pub struct MessageRole {
+    pub emoji: ReactionType,
+    pub role: RoleId,
+    pub slots: Option<i32>,
+    pub hours: Option<i64>,
+}
+
+/// Post a reaction-role message, add all of its reactions and register its reaction-roles.
+///
+/// Returns `None` if the bot couldn't react with one of the emojis. The message gets deleted in that
+/// case.
+pub async fn create_reaction_message(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    title: &str,
+    description: &str,
+    roles: &[MessageRole],
+) -> Result<Option<Message>, KowalskiError> {
+    // Post the message and add all reactions
+    let embed = create_embed(title, description);
+    let message = channel_id
+        .send_message(&ctx.http, |message| message.set_embeds(vec![embed]))
+        .await?;
+
+    let emojis: Vec<_> = roles
+        .iter()
+        .map(|role| role.emoji.clone())
+        .unique()
+        .collect();
+    for emoji in emojis {
+        if message.react(&ctx.http, emoji).await.is_err() {
+            message.delete(&ctx.http).await?;
+
+            return Ok(None);
+        }
+    }
+
+    // Get guild, channel and message ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let channel_db_id = database.get_channel(guild_id, channel_id).await?;
+    let message_db_id = database
+        .get_message(guild_id, channel_id, message.id)
+        .await?;
+
+    // Get emoji and role ids
+    let mut emoji_db_ids = Vec::new();
+    let mut role_db_ids = Vec::new();
+    for role in roles {
+        emoji_db_ids.push(database.get_emoji(guild_id, &role.emoji).await?);
+        role_db_ids.push(database.get_role(guild_id, role.role).await?);
+    }
+    let slots: Vec<_> = roles.iter().map(|role| role.slots).collect();
+    let hours: Vec<_> = roles.iter().map(|role| role.hours).collect();
+
+    // Register the message and all of its reaction-roles at once
+    database
+        .client
+        .execute(
+            "
+            WITH reaction_message AS (
+                INSERT INTO reaction_role_messages
+                VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::TEXT, $5::TEXT)
+            )
+
+            INSERT INTO reaction_roles (guild, channel, message, emoji, role, slots, hours,
+                capacity)
+            SELECT $1::BIGINT, $2::BIGINT, $3::BIGINT, emoji, role, slots, hours, slots
+            FROM UNNEST($6::INT[], $7::BIGINT[], $8::INT[], $9::BIGINT[])
+                AS pairs(emoji, role, slots, hours)
+            ON CONFLICT DO NOTHING
+            ",
+            &[
+                &guild_db_id,
+                &channel_db_id,
+                &message_db_id,
+                &title,
+                &description,
+                &emoji_db_ids,
+                &role_db_ids,
+                &slots,
+                &hours,
+            ],
+        )
+        .await?;
+
+    // Render the reaction-roles into the message
+    update_reaction_message(ctx, database, guild_id, channel_id, message.id).await?;
+
+    Ok(Some(message))
+}
+
+/// Re-render a reaction-role message composed by the bot to list its current reaction-roles.
+///
+/// Note: Messages not composed by the bot are left untouched.
+pub async fn update_reaction_message(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    message_id: MessageId,
+) -> Result<(), KowalskiError> {
+    // Get guild, channel and message ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let channel_db_id = channel_id.0 as i64;
+    let message_db_id = message_id.0 as i64;
+
+    // Get the title and description of the message
+    let (title, description) = match database
+        .client
+        .query_opt(
+            "
+            SELECT title, description FROM reaction_role_messages
+            WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+            ",
+            &[&guild_db_id, &channel_db_id, &message_db_id],
+        )
+        .await?
+    {
+        Some(row) => (row.get::<_, String>(0), row.get::<_, String>(1)),
+        None => return Ok(()),
+    };
+
+    // Get the reaction-roles of the message
+    let roles = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT unicode, guild_emoji, role
+                FROM reaction_roles rr
+                INNER JOIN emojis e ON emoji = id
+                WHERE rr.guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                ORDER BY emoji, role
+                ",
+                &[&guild_db_id, &channel_db_id, &message_db_id],
+            )
+            .await?;
+
+        let mut roles = Vec::new();
+
+        for row in rows {
+            let unicode: Option<String> = row.get(0);
+            let guild_emoji: Option<i64> = row.get(1);
+            let emoji = match (unicode, guild_emoji) {
+                (Some(string), _) => ReactionType::Unicode(string),
+                (_, Some(id)) => {
+                    let emoji = guild_id.emoji(&ctx.http, EmojiId(id as u64)).await?;
+
+                    ReactionType::Custom {
+                        animated: emoji.animated,
+                        id: emoji.id,
+                        name: Some(emoji.name),
+                    }
+                }
+                _ => unreachable!(),
+            };
+            let role_id = RoleId(row.get::<_, i64>(2) as u64);
+
+            roles.push(format!("{} {}", emoji, role_id.mention()));
+        }
+
+        roles
+    };
+
+    let embed = if roles.is_empty() {
+        create_embed(&title, &description)
+    } else {
+        create_embed(&title, &format!("{}\n\n{}", description, roles.join("\n")))
+    };
+
+    channel_id
+        .edit_message(&ctx.http, message_id, |message| {
+            message.set_embeds(vec![embed])
+        })
+        .await?;
+
+    Ok(())
+}
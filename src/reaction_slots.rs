@@ -0,0 +1,397 @@
+use std::{collections::HashMap, time::Duration};
+
+use serenity::{
+    client::Context,
+    model::id::{GuildId, RoleId, UserId},
+    prelude::Mentionable,
+};
+use tokio::time::interval;
+use tracing::error;
+
+use crate::{
+    data, database::client::Database, error::KowalskiError, role_requirements::check_requirements,
+    strings::ERR_REACTION_SLOTS, temporary_roles::add_temporary_role, utils::create_embed,
+};
+
+pub fn check_reaction_slots(ctx: Context, period: Duration) {
+    tokio::spawn(async move {
+        // Get database
+        let database = data!(ctx, Database);
+
+        // Create the interval at which we will reconcile the slots
+        let mut interval = interval(period);
+
+        loop {
+            // Wait for the next tick
+            interval.tick().await;
+
+            let guilds: Vec<_> = match database
+                .client
+                .query(
+                    "
+                    SELECT guild FROM reaction_roles
+                    WHERE capacity IS NOT NULL OR slots IS NOT NULL
+                    UNION
+                    SELECT guild FROM role_menu_roles
+                    WHERE capacity IS NOT NULL
+                    ",
+                    &[],
+                )
+                .await
+            {
+                Ok(rows) => rows
+                    .iter()
+                    .map(|row| GuildId(row.get::<_, i64>(0) as u64))
+                    .collect(),
+                Err(why) => {
+                    error!("{}: {:?}", ERR_REACTION_SLOTS, why);
+                    continue;
+                }
+            };
+
+            for guild_id in guilds {
+                if let Err(why) = reconcile_slots(&ctx, &database, guild_id).await {
+                    error!("{}: {:?}", ERR_REACTION_SLOTS, why);
+                }
+            }
+        }
+    });
+}
+
+/// Recompute the free slots of the limited reaction-roles and role menu roles of a guild from the
+/// members holding them.
+///
+/// Returns the number of roles whose slots have been corrected.
+pub async fn reconcile_slots(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+) -> Result<u64, KowalskiError> {
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    // Count the members holding each role
+    let holders = count_holders(ctx, guild_id).await?;
+
+    // Get the limited reaction-roles
+    let reaction_roles: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT channel, message, emoji, role, capacity, slots FROM reaction_roles
+                WHERE guild = $1::BIGINT AND (capacity IS NOT NULL OR slots IS NOT NULL)
+                ",
+                &[&guild_db_id],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                (
+                    row.get::<_, i64>(0),
+                    row.get::<_, i64>(1),
+                    row.get::<_, i32>(2),
+                    RoleId(row.get::<_, i64>(3) as u64),
+                    row.get::<_, Option<i32>>(4),
+                    row.get::<_, Option<i32>>(5),
+                )
+            })
+            .collect()
+    };
+
+    let mut corrected = 0;
+
+    for (channel_db_id, message_db_id, emoji_db_id, role_id, capacity, slots) in reaction_roles {
+        let held = holders.get(&role_id).copied().unwrap_or_default();
+
+        // Reaction-roles limited before capacities were stored only know their free slots
+        let (capacity, derived) = match capacity {
+            Some(capacity) => (capacity, false),
+            None => (slots.unwrap_or_default() + held, true),
+        };
+        let free = (capacity - held).max(0);
+
+        if slots != Some(free) || derived {
+            database
+                .client
+                .execute(
+                    "
+                    UPDATE reaction_roles
+                    SET slots = $6::INT, capacity = COALESCE(capacity, $7::INT)
+                    WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                    AND emoji = $4::INT AND role = $5::BIGINT
+                    ",
+                    &[
+                        &guild_db_id,
+                        &channel_db_id,
+                        &message_db_id,
+                        &emoji_db_id,
+                        &(role_id.0 as i64),
+                        &free,
+                        &capacity,
+                    ],
+                )
+                .await?;
+
+            corrected += 1;
+        }
+
+        // Hand out freed slots to waiting users
+        if free > 0 {
+            fill_waitlist(
+                ctx,
+                database,
+                guild_id,
+                (channel_db_id, message_db_id, emoji_db_id),
+                role_id,
+            )
+            .await?;
+        }
+    }
+
+    // Get the limited roles of role menus
+    let menu_roles: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT name, role, capacity, slots FROM role_menu_roles
+                WHERE guild = $1::BIGINT AND capacity IS NOT NULL
+                ",
+                &[&guild_db_id],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                (
+                    row.get::<_, String>(0),
+                    RoleId(row.get::<_, i64>(1) as u64),
+                    row.get::<_, i32>(2),
+                    row.get::<_, Option<i32>>(3),
+                )
+            })
+            .collect()
+    };
+
+    for (name, role_id, capacity, slots) in menu_roles {
+        let free = (capacity - holders.get(&role_id).copied().unwrap_or_default()).max(0);
+
+        if slots != Some(free) {
+            database
+                .client
+                .execute(
+                    "
+                    UPDATE role_menu_roles
+                    SET slots = $4::INT
+                    WHERE guild = $1::BIGINT AND name = $2::TEXT AND role = $3::BIGINT
+                    ",
+                    &[&guild_db_id, &name, &(role_id.0 as i64), &free],
+                )
+                .await?;
+
+            corrected += 1;
+        }
+    }
+
+    Ok(corrected)
+}
+
+/// Count the members holding each role of a guild.
+///
+/// Note: The cache might only know part of the members, in which case they are fetched instead.
+async fn count_holders(
+    ctx: &Context,
+    guild_id: GuildId,
+) -> Result<HashMap<RoleId, i32>, KowalskiError> {
+    let mut holders: HashMap<RoleId, i32> = HashMap::new();
+
+    let cached = guild_id
+        .to_guild_cached(ctx)
+        .filter(|guild| guild.members.len() as u64 >= guild.member_count);
+
+    match cached {
+        Some(guild) => {
+            for member in guild.members.values() {
+                for role in &member.roles {
+                    *holders.entry(*role).or_default() += 1;
+                }
+            }
+        }
+        None => {
+            let mut after = None;
+
+            loop {
+                let members = guild_id.members(&ctx.http, Some(1000), after).await?;
+
+                for member in &members {
+                    for role in &member.roles {
+                        *holders.entry(*role).or_default() += 1;
+                    }
+                }
+
+                match members.last() {
+                    Some(member) if members.len() == 1000 => after = Some(member.user.id),
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    Ok(holders)
+}
+
+/// Assign a reaction-role to the users on its waitlist as long as there are free slots.
+pub async fn fill_waitlist(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    (channel_db_id, message_db_id, emoji_db_id): (i64, i64, i32),
+    role_id: RoleId,
+) -> Result<(), KowalskiError> {
+    // Get guild and role ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let role_db_id = database.get_role(guild_id, role_id).await?;
+
+    loop {
+        // Get the user waiting the longest
+        let user_id = match database
+            .client
+            .query_opt(
+                "
+                SELECT \"user\" FROM reaction_role_waitlist
+                WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                AND emoji = $4::INT AND role = $5::BIGINT
+                ORDER BY time, \"user\"
+                LIMIT 1
+                ",
+                &[
+                    &guild_db_id,
+                    &channel_db_id,
+                    &message_db_id,
+                    &emoji_db_id,
+                    &role_db_id,
+                ],
+            )
+            .await?
+        {
+            Some(row) => UserId(row.get::<_, i64>(0) as u64),
+            None => break,
+        };
+
+        // Take a slot if there are any left
+        let hours = match database
+            .client
+            .query_opt(
+                "
+                UPDATE reaction_roles
+                SET slots = slots - 1
+                WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                AND emoji = $4::INT AND role = $5::BIGINT AND slots > 0
+                RETURNING hours
+                ",
+                &[
+                    &guild_db_id,
+                    &channel_db_id,
+                    &message_db_id,
+                    &emoji_db_id,
+                    &role_db_id,
+                ],
+            )
+            .await?
+        {
+            Some(row) => row.get::<_, Option<i64>>(0),
+            None => break,
+        };
+
+        database
+            .client
+            .execute(
+                "
+                DELETE FROM reaction_role_waitlist
+                WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+                AND emoji = $4::INT AND role = $5::BIGINT AND \"user\" = $6::BIGINT
+                ",
+                &[
+                    &guild_db_id,
+                    &channel_db_id,
+                    &message_db_id,
+                    &emoji_db_id,
+                    &role_db_id,
+                    &(user_id.0 as i64),
+                ],
+            )
+            .await?;
+
+        // Skip users who left, already hold the role or are no longer allowed to pick it
+        let member = guild_id.member(ctx, user_id).await.ok();
+        let rejection = match &member {
+            Some(member) if !member.roles.contains(&role_id) => {
+                check_requirements(database, guild_id, member, role_id).await?
+            }
+            _ => Some(String::new()),
+        };
+
+        match (member, rejection) {
+            (Some(mut member), None) => {
+                member.add_role(&ctx.http, role_id).await?;
+
+                // Schedule the removal of temporary roles
+                if let Some(hours) = hours {
+                    add_temporary_role(database, guild_id, user_id, role_id, hours).await?;
+                }
+
+                notify(
+                    ctx,
+                    user_id,
+                    &format!(
+                        "A slot of the role {} has become available, so you have received it.",
+                        role_id.mention()
+                    ),
+                )
+                .await;
+            }
+            (_, rejection) => {
+                // Free the slot again
+                database
+                    .client
+                    .execute(
+                        "
+                        UPDATE reaction_roles
+                        SET slots = slots + 1
+                        WHERE guild = $1::BIGINT AND channel = $2::BIGINT
+                        AND message = $3::BIGINT AND emoji = $4::INT AND role = $5::BIGINT
+                        ",
+                        &[
+                            &guild_db_id,
+                            &channel_db_id,
+                            &message_db_id,
+                            &emoji_db_id,
+                            &role_db_id,
+                        ],
+                    )
+                    .await?;
+
+                if let Some(reason) = rejection.filter(|reason| !reason.is_empty()) {
+                    notify(ctx, user_id, &reason).await;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Inform a user about the state of a reaction-role.
+///
+/// Note: Users might not accept direct messages, so failures are ignored.
+pub async fn notify(ctx: &Context, user_id: UserId, content: &str) {
+    let embed = create_embed("Reaction role", content);
+
+    if let Ok(channel) = user_id.create_dm_channel(&ctx.http).await {
+        let _ = channel
+            .send_message(&ctx.http, |message| message.set_embed(embed))
+            .await;
+    }
+}
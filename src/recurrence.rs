@@ -0,0 +1,414 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use itertools::Itertools;
+
+use crate::{error::KowalskiError, strings::ERR_CMD_ARGS_INVALID, time_parser::resolve};
+
+/// Number of days to look ahead when searching for the next occurrence of a cron expression.
+const CRON_LOOKAHEAD_DAYS: i64 = 5 * 366;
+
+const WEEKDAYS: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+/// Rule describing when a recurring reminder fires again.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Recurrence {
+    Daily,
+    /// Weekly on the given weekdays (counted from Monday), or on the weekday of the previous
+    /// occurrence if empty.
+    Weekly(Vec<u32>),
+    /// Monthly on the day of the previous occurrence, skipping months without that day.
+    Monthly,
+    Cron(Cron),
+}
+
+/// Cron-like expression consisting of minute, hour, day of month, month and day of week.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cron {
+    expression: String,
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    // Whether the day of month and day of week fields are restricted
+    days_restricted: bool,
+    weekdays_restricted: bool,
+}
+
+impl Recurrence {
+    /// Describe the rule in a human-readable way.
+    pub fn describe(&self) -> String {
+        match self {
+            Recurrence::Daily => "every day".to_string(),
+            Recurrence::Weekly(weekdays) if weekdays.is_empty() => "every week".to_string(),
+            Recurrence::Weekly(weekdays) => format!(
+                "every week on {}",
+                weekdays
+                    .iter()
+                    .map(|weekday| WEEKDAY_NAMES[*weekday as usize])
+                    .join(", ")
+            ),
+            Recurrence::Monthly => "every month".to_string(),
+            Recurrence::Cron(cron) => format!("according to `{}`", cron.expression),
+        }
+    }
+
+    /// Get the first occurrence following the previous one which lies after the given point in
+    /// time. Days and times are interpreted in the time zone of the previous occurrence.
+    pub fn next_after<Tz: TimeZone>(
+        &self,
+        previous: DateTime<Tz>,
+        after: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let tz = previous.timezone();
+        let mut local = previous.naive_local();
+
+        loop {
+            local = match self {
+                Recurrence::Daily => local + Duration::days(1),
+                Recurrence::Weekly(weekdays) => {
+                    let weekday = local.weekday().num_days_from_monday();
+                    let days = (1..=7)
+                        .find(|days| {
+                            let next = (weekday + days) % 7;
+                            weekdays.is_empty() && next == weekday || weekdays.contains(&next)
+                        })
+                        .unwrap_or(7);
+
+                    local + Duration::days(days as i64)
+                }
+                Recurrence::Monthly => next_month(local)?,
+                Recurrence::Cron(cron) => cron.next_after(local)?,
+            };
+
+            let next = resolve(&tz, local)?;
+            if next > after {
+                return Some(next);
+            }
+        }
+    }
+}
+
+impl FromStr for Recurrence {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        let (kind, arguments) = s.split_once(' ').unwrap_or((&s, ""));
+
+        match (kind, arguments.trim()) {
+            ("daily", "") => Ok(Recurrence::Daily),
+            ("weekly", "") => Ok(Recurrence::Weekly(Vec::new())),
+            ("weekly", arguments) => {
+                let weekdays = arguments
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|weekday| !weekday.is_empty())
+                    .map(|weekday| {
+                        WEEKDAYS
+                            .iter()
+                            .position(|name| weekday.starts_with(name))
+                            .map(|position| position as u32)
+                            .ok_or_else(|| KowalskiError::DiscordApiError(invalid(&s)))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Ok(Recurrence::Weekly(
+                    weekdays.into_iter().sorted().dedup().collect(),
+                ))
+            }
+            ("monthly", "") => Ok(Recurrence::Monthly),
+            ("cron", arguments) => Ok(Recurrence::Cron(arguments.parse()?)),
+            // Allow plain cron expressions
+            _ if s.split_whitespace().count() == 5 => Ok(Recurrence::Cron(s.parse()?)),
+            _ => Err(KowalskiError::DiscordApiError(invalid(&s))),
+        }
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Recurrence::Daily => write!(f, "daily"),
+            Recurrence::Weekly(weekdays) if weekdays.is_empty() => write!(f, "weekly"),
+            Recurrence::Weekly(weekdays) => write!(
+                f,
+                "weekly {}",
+                weekdays
+                    .iter()
+                    .map(|weekday| WEEKDAYS[*weekday as usize])
+                    .join(",")
+            ),
+            Recurrence::Monthly => write!(f, "monthly"),
+            Recurrence::Cron(cron) => write!(f, "cron {}", cron.expression),
+        }
+    }
+}
+
+impl Cron {
+    fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.date();
+
+        for offset in 0..CRON_LOOKAHEAD_DAYS {
+            let date = start + Duration::days(offset);
+
+            if !self.matches_date(date) {
+                continue;
+            }
+
+            for hour in 0..24 {
+                if self.hours & (1 << hour) == 0 {
+                    continue;
+                }
+
+                for minute in 0..60 {
+                    if self.minutes & (1 << minute) == 0 {
+                        continue;
+                    }
+
+                    let datetime = date.and_hms_opt(hour, minute, 0)?;
+                    if datetime > after {
+                        return Some(datetime);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+
+        // Like cron, match either of the fields if both of them are restricted
+        match (self.days_restricted, self.weekdays_restricted) {
+            (true, true) => day || weekday,
+            _ => day && weekday,
+        }
+    }
+}
+
+impl FromStr for Cron {
+    type Err = KowalskiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<_> = s.split_whitespace().collect();
+
+        if fields.len() != 5 {
+            return Err(KowalskiError::DiscordApiError(invalid(s)));
+        }
+
+        let parse = |field: &str, min: u32, max: u32| {
+            parse_field(field, min, max).ok_or_else(|| KowalskiError::DiscordApiError(invalid(s)))
+        };
+
+        let mut weekdays = parse(fields[4], 0, 7)?;
+        // Both 0 and 7 refer to Sunday
+        if weekdays & (1 << 7) != 0 {
+            weekdays |= 1;
+        }
+
+        Ok(Cron {
+            expression: fields.join(" "),
+            minutes: parse(fields[0], 0, 59)?,
+            hours: parse(fields[1], 0, 23)?,
+            days: parse(fields[2], 1, 31)?,
+            months: parse(fields[3], 1, 12)?,
+            weekdays,
+            days_restricted: fields[2] != "*",
+            weekdays_restricted: fields[4] != "*",
+        })
+    }
+}
+
+// Parse a cron field (e.g. "*", "1-5", "*/15" or "0,30") into a bit set
+fn parse_field(field: &str, min: u32, max: u32) -> Option<u64> {
+    let mut set = 0;
+
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, step.parse().ok().filter(|step| *step > 0)?),
+            None => (part, 1),
+        };
+
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (start.parse().ok()?, end.parse().ok()?),
+                None => {
+                    let value = range.parse().ok()?;
+                    // A single value with a step runs until the end of the range
+                    (value, if step > 1 { max } else { value })
+                }
+            },
+        };
+
+        if start < min || end > max || start > end {
+            return None;
+        }
+
+        for value in (start..=end).step_by(step) {
+            set |= 1 << value;
+        }
+    }
+
+    Some(set)
+}
+
+// Get the same day and time in the next month which has that day
+fn next_month(datetime: NaiveDateTime) -> Option<NaiveDateTime> {
+    let (mut year, mut month) = (datetime.year(), datetime.month());
+
+    for _ in 0..12 {
+        if month == 12 {
+            year += 1;
+            month = 1;
+        } else {
+            month += 1;
+        }
+
+        if let Some(date) = NaiveDate::from_ymd_opt(year, month, datetime.day()) {
+            return date.and_hms_opt(datetime.hour(), datetime.minute(), datetime.second());
+        }
+    }
+
+    None
+}
+
+fn invalid(recurrence: &str) -> String {
+    format!("{}: {}", ERR_CMD_ARGS_INVALID, recurrence)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
+    use chrono_tz::Europe::Berlin;
+
+    use super::Recurrence;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn parse_rules() {
+        assert_eq!("daily".parse::<Recurrence>().unwrap(), Recurrence::Daily);
+        assert_eq!(
+            "Weekly friday, mon, fri".parse::<Recurrence>().unwrap(),
+            Recurrence::Weekly(vec![0, 4])
+        );
+        assert_eq!(
+            "monthly".parse::<Recurrence>().unwrap(),
+            Recurrence::Monthly
+        );
+        assert!(matches!(
+            "0 9 * * 1-5".parse::<Recurrence>().unwrap(),
+            Recurrence::Cron(_)
+        ));
+
+        assert!("hourly".parse::<Recurrence>().is_err());
+        assert!("weekly someday".parse::<Recurrence>().is_err());
+        assert!("cron 60 * * * *".parse::<Recurrence>().is_err());
+        assert!("cron */0 * * * *".parse::<Recurrence>().is_err());
+        assert!("cron 0 9 * *".parse::<Recurrence>().is_err());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for rule in [
+            "daily",
+            "weekly",
+            "weekly mon,fri",
+            "monthly",
+            "cron */15 9-17 * * 1-5",
+        ] {
+            let recurrence: Recurrence = rule.parse().unwrap();
+
+            assert_eq!(recurrence.to_string(), rule);
+            assert_eq!(
+                recurrence.to_string().parse::<Recurrence>().unwrap(),
+                recurrence
+            );
+        }
+    }
+
+    #[test]
+    fn daily_keeps_local_time_across_daylight_saving_time() {
+        let previous = Berlin.with_ymd_and_hms(2026, 3, 28, 9, 0, 0).unwrap();
+
+        assert_eq!(
+            Recurrence::Daily.next_after(previous, previous.with_timezone(&Utc)),
+            Some(utc(2026, 3, 29, 7, 0))
+        );
+    }
+
+    #[test]
+    fn weekly() {
+        // Wednesday
+        let previous = Berlin.with_ymd_and_hms(2026, 3, 25, 9, 0, 0).unwrap();
+        let after = previous.with_timezone(&Utc);
+
+        assert_eq!(
+            Recurrence::Weekly(Vec::new()).next_after(previous, after),
+            Some(utc(2026, 4, 1, 7, 0))
+        );
+        assert_eq!(
+            Recurrence::Weekly(vec![0, 4]).next_after(previous, after),
+            Some(utc(2026, 3, 27, 8, 0))
+        );
+    }
+
+    #[test]
+    fn monthly_skips_months_without_the_day() {
+        let previous = Berlin.with_ymd_and_hms(2026, 1, 31, 9, 0, 0).unwrap();
+
+        assert_eq!(
+            Recurrence::Monthly.next_after(previous, previous.with_timezone(&Utc)),
+            Some(utc(2026, 3, 31, 7, 0))
+        );
+    }
+
+    #[test]
+    fn cron() {
+        let recurrence: Recurrence = "0 9 * * 1-5".parse().unwrap();
+        // Friday, 10:00
+        let previous = Berlin.with_ymd_and_hms(2026, 3, 27, 10, 0, 0).unwrap();
+
+        assert_eq!(
+            recurrence.next_after(previous, previous.with_timezone(&Utc)),
+            Some(utc(2026, 3, 30, 7, 0))
+        );
+
+        // Either the day of month or the day of week has to match if both are restricted
+        let recurrence: Recurrence = "30 8 1 * 0".parse().unwrap();
+        assert_eq!(
+            recurrence.next_after(previous, previous.with_timezone(&Utc)),
+            Some(utc(2026, 3, 29, 6, 30))
+        );
+    }
+
+    #[test]
+    fn skips_missed_occurrences() {
+        let previous = Berlin.with_ymd_and_hms(2026, 3, 25, 9, 0, 0).unwrap();
+
+        assert_eq!(
+            Recurrence::Daily.next_after(previous, utc(2026, 4, 10, 12, 0)),
+            Some(utc(2026, 4, 11, 7, 0))
+        );
+    }
+}
@@ -1,82 +1,748 @@
-use std::time::Duration;
+use std::{
+    future::pending,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
+use chrono::{DateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use itertools::Itertools;
 use serenity::{
+    builder::{CreateActionRow, CreateEmbed},
     client::Context,
-    model::id::{ChannelId, GuildId, MessageId, UserId},
-    prelude::Mentionable,
-    prelude::SerenityError,
+    model::{
+        id::{ChannelId, GuildId, MessageId, RoleId, UserId},
+        interactions::{
+            message_component::{ButtonStyle, MessageComponentInteraction},
+            InteractionResponseType,
+        },
+    },
+    prelude::{Mentionable, TypeMapKey},
+    utils::Colour,
+};
+use strum_macros::{Display, EnumString};
+use tokio::{
+    sync::{Mutex, Notify},
+    time::sleep,
 };
-use tokio::time::interval;
+use tokio_postgres::Row;
 use tracing::error;
 
-use crate::{data, database::client::Database, strings::ERR_REMINDER, utils::create_embed};
+use crate::{
+    data,
+    database::{client::Database, types::ModuleStatus},
+    error::KowalskiError,
+    error::KowalskiError::DiscordApiError,
+    pluralize,
+    recurrence::Recurrence,
+    strings::{ERR_CMD_ARGS_INVALID, ERR_GUILD_NOT_CACHED, ERR_REMINDER},
+    time_parser::{parse_time, DATE_FORMAT},
+    utils::create_embed,
+};
+
+/// Prefix of the custom ids of the buttons on delivered reminders.
+pub const REMINDER_ID: &str = "reminder";
+
+/// Custom id of the component used to edit a pending reminder.
+pub const EDIT_REMINDER_ID: &str = "editreminder";
+
+/// Custom id of the component used to pick the time of a reminder about a message.
+pub const PICK_TIME_ID: &str = "picktime";
+
+/// Number of seconds a reminder may be late without mentioning it.
+const LATE_SECONDS: i64 = 60;
+
+/// Number of seconds to wait before retrying to get the next reminder or to send a reminder.
+const RETRY_SECONDS: i64 = 60;
+
+/// Number of hours to keep retrying a reminder that couldn't be delivered.
+const GIVE_UP_HOURS: i64 = 24;
+
+/// Snooze durations offered on delivered reminders, in minutes.
+const SNOOZE_MINUTES: [(i64, &str); 3] = [(10, "10m"), (60, "1h"), (1440, "1d")];
+
+/// Where a reminder gets delivered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Delivery {
+    #[default]
+    Channel,
+    Dm,
+    Both,
+}
+
+/// Repetition of a reminder as given by the user.
+pub struct Repetition {
+    pub recurrence: Recurrence,
+    pub ends: Option<DateTime<Utc>>,
+    pub count: Option<i64>,
+}
+
+impl Repetition {
+    /// Parse the repetition options of a reminder first sent at the given point in time, returning
+    /// a message for the user if they are invalid.
+    pub fn parse<T: TimeZone>(
+        repeat: Option<String>,
+        until: Option<String>,
+        count: Option<i64>,
+        first: DateTime<Utc>,
+        now: DateTime<T>,
+    ) -> Result<Option<Self>, &'static str> {
+        let recurrence = match repeat.map(|repeat| repeat.parse::<Recurrence>()) {
+            Some(Ok(recurrence)) => Some(recurrence),
+            Some(Err(_)) => {
+                return Err("I don't know how to repeat the reminder this way. \
+                    Try \"daily\", \"weekly\", \"weekly mon,fri\", \"monthly\" \
+                    or a cron expression like \"0 9 * * 1-5\".")
+            }
+            None => None,
+        };
+
+        let ends =
+            match until.map(|until| parse_time(&until, now)) {
+                Some(Some(ends)) if ends > first => Some(ends),
+                Some(_) => return Err(
+                    "The end of the repetition has to be a point in time after the first reminder.",
+                ),
+                None => None,
+            };
+
+        match recurrence {
+            Some(recurrence) => Ok(Some(Repetition {
+                recurrence,
+                ends,
+                count,
+            })),
+            None if ends.is_some() || count.is_some() => Err(
+                "Please also tell me how to repeat the reminder if you want to limit the repetitions.",
+            ),
+            None => Ok(None),
+        }
+    }
 
-pub fn check_reminders(ctx: Context, period: Duration) {
+    /// Get how often the reminder gets repeated after the first time.
+    pub fn remaining(&self) -> Option<i32> {
+        self.count.map(|count| count as i32 - 1)
+    }
+
+    /// Describe the repetition in a human-readable way.
+    pub fn describe(&self, time_zone: &Tz) -> String {
+        let mut description = format!("repeats {}", self.recurrence.describe());
+        if let Some(ends) = self.ends {
+            description.push_str(&format!(
+                " until {}",
+                ends.with_timezone(time_zone).format(DATE_FORMAT)
+            ));
+        }
+        if let Some(count) = self.count {
+            description.push_str(&format!(", {} in total", pluralize!("time", count)));
+        }
+
+        description
+    }
+}
+
+/// Scheduler keeping track of the point in time the next reminder is due.
+#[derive(Default)]
+pub struct ReminderScheduler {
+    next: Mutex<Option<DateTime<Utc>>>,
+    notify: Notify,
+    started: AtomicBool,
+}
+
+impl ReminderScheduler {
+    /// Wake the scheduler early if a reminder is due before the next known one.
+    pub async fn schedule(&self, time: DateTime<Utc>) {
+        let mut next = self.next.lock().await;
+
+        if next.is_none_or(|next| time < next) {
+            *next = Some(time);
+            self.notify.notify_one();
+        }
+    }
+}
+
+impl TypeMapKey for ReminderScheduler {
+    type Value = Arc<ReminderScheduler>;
+}
+
+pub fn check_reminders(ctx: Context) {
     tokio::spawn(async move {
-        // Get database
-        let database = data!(ctx, Database);
+        // Get database and scheduler
+        let (database, scheduler) = data!(ctx, (Database, ReminderScheduler));
 
-        // Create the interval at which we will check for reminders
-        let mut interval = interval(period);
+        // The ready event fires again after reconnecting, but only one scheduler may run
+        if scheduler.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
 
         loop {
-            // Wait for the next tick
-            interval.tick().await;
+            // Send due reminders, including the ones missed while being offline
+            let result = send_reminders(&ctx, &database).await;
+
+            // Hold the lock while getting the next reminder, so none scheduled meanwhile is lost
+            {
+                let mut next = scheduler.next.lock().await;
 
-            if let Err(why) = send_reminders(&ctx, &database).await {
-                error!("{}: {}", ERR_REMINDER, why);
+                *next = match result {
+                    Ok(()) => get_next_reminder(&database).await,
+                    Err(why) => Err(why),
+                }
+                .unwrap_or_else(|why| {
+                    // Retry later on failure
+                    error!("{}: {:?}", ERR_REMINDER, why);
+                    Some(Utc::now() + chrono::Duration::seconds(RETRY_SECONDS))
+                });
+            }
+
+            // Sleep until the next reminder is due, waking up early for earlier reminders
+            loop {
+                let next = *scheduler.next.lock().await;
+
+                let sleep_until = async {
+                    match next {
+                        Some(next) => sleep((next - Utc::now()).to_std().unwrap_or_default()).await,
+                        None => pending().await,
+                    }
+                };
+
+                tokio::select! {
+                    _ = sleep_until => break,
+                    _ = scheduler.notify.notified() => {}
+                }
             }
         }
     });
 }
 
-async fn send_reminders(ctx: &Context, database: &Database) -> Result<(), SerenityError> {
+async fn get_next_reminder(database: &Database) -> Result<Option<DateTime<Utc>>, KowalskiError> {
+    let row = database
+        .client
+        .query_one("SELECT MIN(COALESCE(retry, time)) FROM reminders", &[])
+        .await?;
+
+    Ok(row.get(0))
+}
+
+async fn send_reminders(ctx: &Context, database: &Database) -> Result<(), KowalskiError> {
     // Get outstanding reminders
-    let reminders = {
-        let rows = database
-            .client
-            .query(
-                "
-                    DELETE FROM reminders
-                    WHERE time <= NOW()
-                    RETURNING guild, channel, message, \"user\", content
-                    ",
-                &[],
-            )
-            .await
+    let rows = database
+        .client
+        .query(
+            "
+            SELECT guild, channel, message, r.\"user\", time, content, recurrence, ends,
+                remaining, time_zone, COALESCE(r.delivery, us.delivery), announcement, role, title,
+                image, id
+            FROM reminders r
+            LEFT JOIN user_settings us ON r.\"user\" = us.\"user\"
+            WHERE COALESCE(retry, time) <= $1::TIMESTAMPTZ
+            ",
+            &[&Utc::now()],
+        )
+        .await?;
+
+    for row in rows {
+        let guild_id = row
+            .get::<_, Option<i64>>(0)
+            .map(|guild| GuildId(guild as u64));
+        let channel_id = ChannelId(row.get::<_, i64>(1) as u64);
+        // Announcements don't refer to a message
+        let message_id = row
+            .get::<_, Option<i64>>(2)
+            .map(|message| MessageId(message as u64));
+        let user_id = UserId(row.get::<_, i64>(3) as u64);
+        let time = row.get::<_, DateTime<Utc>>(4);
+        let content = row.get::<_, String>(5);
+        let delivery = row
+            .get::<_, Option<String>>(10)
+            .and_then(|delivery| delivery.parse().ok())
             .unwrap_or_default();
 
-        rows.iter()
-            .map(|row| {
-                let guild_id = GuildId(row.get::<_, i64>(0) as u64);
-                let channel_id = ChannelId(row.get::<_, i64>(1) as u64);
-                let message_id = MessageId(row.get::<_, i64>(2) as u64);
-                let user_id = UserId(row.get::<_, i64>(3) as u64);
-                let content = row.get::<_, String>(4);
+        let result = match guild_id {
+            // Announcements are only sent to their channel
+            Some(_) if row.get::<_, bool>(11) => {
+                let role_id = row
+                    .get::<_, Option<i64>>(12)
+                    .map(|role| RoleId(role as u64));
+
+                let mut embed = create_embed(
+                    row.get::<_, Option<&str>>(13).unwrap_or("Announcement"),
+                    &content,
+                );
+                embed.color(Colour::BLURPLE);
+                if let Some(image) = row.get::<_, Option<&str>>(14) {
+                    embed.image(image);
+                }
+                note_lateness(&mut embed, time);
+
+                send_announcement(ctx, channel_id, role_id, embed).await
+            }
+            Some(guild_id) => {
+                let mut embed = create_embed("Reminder", &content);
+                note_lateness(&mut embed, time);
+
+                // Try the channel first, unless the user only wants direct messages
+                let in_channel = match delivery {
+                    Delivery::Dm => false,
+                    _ => match send_to_channel(
+                        ctx,
+                        guild_id,
+                        channel_id,
+                        message_id,
+                        user_id,
+                        embed.clone(),
+                    )
+                    .await
+                    {
+                        Ok(delivered) => delivered,
+                        Err(why) => {
+                            error!("{}: {:?}", ERR_REMINDER, why);
+
+                            false
+                        }
+                    },
+                };
+
+                if delivery == Delivery::Channel && in_channel {
+                    Ok(())
+                } else {
+                    // Fall back to a direct message if the channel couldn't be used
+                    match send_to_user(ctx, user_id, None, embed).await {
+                        Err(why) if in_channel => {
+                            error!("{}: {:?}", ERR_REMINDER, why);
+
+                            Ok(())
+                        }
+                        result => result,
+                    }
+                }
+            }
+            // Reminders set in direct messages are sent there
+            None => {
+                let mut embed = create_embed("Reminder", &content);
+                note_lateness(&mut embed, time);
+
+                let reference = message_id.map(|message_id| (channel_id, message_id));
+
+                send_to_user(ctx, user_id, reference, embed).await
+            }
+        };
+
+        // Only move on to the next occurrence once the reminder has been delivered
+        match result {
+            Ok(()) => reschedule(database, &row).await?,
+            Err(why) => {
+                error!("{}: {:?}", ERR_REMINDER, why);
+
+                postpone(database, &row).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Send a reminder to the channel it has been set in.
+///
+/// Returns `false` if the channel no longer exists or the user can't see it anymore.
+async fn send_to_channel(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+    message_id: Option<MessageId>,
+    user_id: UserId,
+    embed: CreateEmbed,
+) -> Result<bool, KowalskiError> {
+    // Check whether the channel still exists
+    let channel = match ctx.cache.guild_channel(channel_id) {
+        Some(channel) => channel,
+        None => return Ok(false),
+    };
+
+    // Check whether the user can still see the channel
+    let member = guild_id.member(ctx, user_id).await?;
+    let permissions = ctx
+        .cache
+        .guild_field(guild_id, |guild| {
+            guild.user_permissions_in(&channel, &member).ok()
+        })
+        .flatten()
+        .ok_or_else(|| DiscordApiError(ERR_GUILD_NOT_CACHED.to_string()))?;
+    if !permissions.view_channel() {
+        return Ok(false);
+    }
+
+    let scheduled_message = match message_id {
+        Some(message_id) => channel_id.message(&ctx.http, message_id).await.ok(),
+        None => None,
+    };
+
+    channel
+        .send_message(&ctx.http, |message| {
+            if let Some(scheduled_message) = scheduled_message {
+                message.reference_message((channel_id, scheduled_message.id));
+            }
+
+            message
+                .content(user_id.mention())
+                .set_embeds(vec![embed])
+                .components(|components| components.set_action_row(reminder_buttons(user_id)))
+        })
+        .await?;
+
+    Ok(true)
+}
+
+async fn send_announcement(
+    ctx: &Context,
+    channel_id: ChannelId,
+    role_id: Option<RoleId>,
+    embed: CreateEmbed,
+) -> Result<(), KowalskiError> {
+    channel_id
+        .send_message(&ctx.http, |message| {
+            // Only ping the role of the announcement
+            if let Some(role_id) = role_id {
+                message
+                    .content(role_id.mention())
+                    .allowed_mentions(|mentions| mentions.roles(vec![role_id]));
+            }
+
+            message.set_embeds(vec![embed])
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn send_to_user(
+    ctx: &Context,
+    user_id: UserId,
+    reference: Option<(ChannelId, MessageId)>,
+    embed: CreateEmbed,
+) -> Result<(), KowalskiError> {
+    let channel = user_id.create_dm_channel(&ctx.http).await?;
 
-                (guild_id, channel_id, message_id, user_id, content)
+    channel
+        .send_message(&ctx.http, |message| {
+            if let Some(reference) = reference {
+                message.reference_message(reference);
+            }
+
+            message
+                .set_embeds(vec![embed])
+                .components(|components| components.set_action_row(reminder_buttons(user_id)))
+        })
+        .await?;
+
+    Ok(())
+}
+
+// Mention in the footer of a reminder how late it is, e.g. after the bot was offline
+fn note_lateness(embed: &mut CreateEmbed, time: DateTime<Utc>) {
+    let late = Utc::now() - time;
+
+    if late.num_seconds() >= LATE_SECONDS {
+        let units = [
+            (late.num_days(), "day"),
+            (late.num_hours() % 24, "hour"),
+            (late.num_minutes() % 60, "minute"),
+        ];
+        let description = units
+            .iter()
+            .filter(|(amount, _)| *amount > 0)
+            .map(|(amount, unit)| pluralize!(unit, *amount))
+            .join(", ");
+
+        embed.footer(|footer| footer.text(format!("Sorry, I'm {} late!", description)));
+    }
+}
+
+// Move a reminder to its next occurrence or delete it if there is none
+async fn reschedule(database: &Database, row: &Row) -> Result<(), KowalskiError> {
+    let id = row.get::<_, i32>(15);
+    let time = row.get::<_, DateTime<Utc>>(4);
+    let recurrence = row.get::<_, Option<String>>(6);
+    let ends = row.get::<_, Option<DateTime<Utc>>>(7);
+    let remaining = row.get::<_, Option<i32>>(8);
+    // Repeat the reminder in the time zone of the user
+    let time_zone = row
+        .get::<_, Option<String>>(9)
+        .and_then(|time_zone| time_zone.parse().ok())
+        .unwrap_or(Tz::UTC);
+
+    let next = match (recurrence, remaining) {
+        (_, Some(0)) | (None, _) => None,
+        (Some(recurrence), _) => recurrence
+            .parse::<Recurrence>()
+            .ok()
+            .and_then(|recurrence| {
+                recurrence.next_after(time.with_timezone(&time_zone), Utc::now())
             })
-            .collect::<Vec<_>>()
+            .filter(|next| ends.is_none_or(|ends| *next <= ends)),
     };
 
-    for (guild_id, channel_id, message_id, user_id, content) in reminders {
-        let channels = guild_id.channels(&ctx.http).await?;
+    match next {
+        Some(next) => {
+            database
+                .client
+                .execute(
+                    "
+                    UPDATE reminders
+                    SET time = $2::TIMESTAMPTZ, remaining = remaining - 1, retry = NULL
+                    WHERE id = $1::INT
+                    ",
+                    &[&id, &next],
+                )
+                .await?;
+        }
+        None => {
+            database
+                .client
+                .execute("DELETE FROM reminders WHERE id = $1::INT", &[&id])
+                .await?;
+        }
+    }
 
-        if let Some(channel) = channels.get(&channel_id) {
-            let scheduled_message = channel_id.message(&ctx.http, message_id).await;
+    Ok(())
+}
 
-            channel
-                .send_message(&ctx.http, |message| {
-                    if let Ok(scheduled_message) = scheduled_message {
-                        message.reference_message((channel_id, scheduled_message.id));
-                    }
+// Retry a reminder that couldn't be delivered later or give up on it after a while
+async fn postpone(database: &Database, row: &Row) -> Result<(), KowalskiError> {
+    let id = row.get::<_, i32>(15);
+    let time = row.get::<_, DateTime<Utc>>(4);
 
-                    let embed = create_embed("Reminder", &content);
-                    message.content(user_id.mention()).set_embeds(vec![embed])
-                })
+    if Utc::now() - time >= chrono::Duration::hours(GIVE_UP_HOURS) {
+        return reschedule(database, row).await;
+    }
+
+    let retry = Utc::now() + chrono::Duration::seconds(RETRY_SECONDS);
+    database
+        .client
+        .execute(
+            "UPDATE reminders SET retry = $2::TIMESTAMPTZ WHERE id = $1::INT",
+            &[&id, &retry],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Describe the repetition of a reminder, given the index of its recurrence column.
+pub fn describe_repetition(row: &Row, index: usize, time_zone: &Tz) -> Option<String> {
+    let recurrence = row
+        .get::<_, Option<String>>(index)?
+        .parse::<Recurrence>()
+        .ok()?;
+    let ends = row.get::<_, Option<DateTime<Utc>>>(index + 1);
+    let remaining = row.get::<_, Option<i32>>(index + 2);
+
+    let mut description = format!("Repeats {}", recurrence.describe());
+    if let Some(ends) = ends {
+        description.push_str(&format!(
+            " until {}",
+            ends.with_timezone(time_zone).format(DATE_FORMAT)
+        ));
+    }
+    if let Some(remaining) = remaining {
+        description.push_str(&format!(", {} more", pluralize!("time", remaining)));
+    }
+
+    Some(description)
+}
+
+/// Check whether reminders can be set, which is the case in direct messages and in guilds with
+/// the utility module.
+pub async fn reminders_enabled(
+    database: &Database,
+    guild_id: Option<GuildId>,
+) -> Result<bool, KowalskiError> {
+    let guild_id = match guild_id {
+        Some(guild_id) => guild_id,
+        None => return Ok(true),
+    };
+
+    let status = database
+        .client
+        .query_opt(
+            "SELECT status FROM modules WHERE guild = $1::BIGINT",
+            &[&(guild_id.0 as i64)],
+        )
+        .await?
+        .map_or(ModuleStatus::default(), |row| row.get(0));
+
+    Ok(status.utility)
+}
+
+/// Get the guild, channel, message and user ids of a reminder set at the given message.
+///
+/// Note: Reminders set in direct messages don't belong to a guild
+pub async fn get_reminder_ids(
+    database: &Database,
+    guild_id: Option<GuildId>,
+    channel_id: ChannelId,
+    message_id: MessageId,
+    user_id: UserId,
+) -> Result<(Option<i64>, i64, i64, i64), KowalskiError> {
+    match guild_id {
+        Some(guild_id) => Ok((
+            Some(database.get_guild(guild_id).await?),
+            database.get_channel(guild_id, channel_id).await?,
+            database
+                .get_message(guild_id, channel_id, message_id)
+                .await?,
+            database.get_user(guild_id, user_id).await?,
+        )),
+        None => Ok((
+            None,
+            channel_id.0 as i64,
+            message_id.0 as i64,
+            user_id.0 as i64,
+        )),
+    }
+}
+
+/// Snooze, repeat or acknowledge a delivered reminder.
+pub async fn answer_reminder(
+    ctx: &Context,
+    component: &MessageComponentInteraction,
+) -> Result<(), KowalskiError> {
+    // Get database and scheduler
+    let (database, scheduler) = data!(ctx, (Database, ReminderScheduler));
+
+    // The custom id consists of the prefix, the action, its argument and the user
+    let parts: Vec<_> = component.data.custom_id.split(':').collect();
+    let (action, argument, user_id) = match parts[..] {
+        [_, action, argument, user] => (action, argument, user.parse().ok().map(UserId)),
+        _ => return Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+    };
+
+    if user_id != Some(component.user.id) {
+        return respond(
+            ctx,
+            component,
+            "Only the user who scheduled the reminder can use these buttons.",
+        )
+        .await;
+    }
+
+    let content = component
+        .message
+        .embeds
+        .first()
+        .and_then(|embed| embed.description.clone())
+        .unwrap_or_default();
+
+    // Get the next point in time to send the reminder at
+    let time_zone = database.get_time_zone(component.user.id).await?;
+    let now = Utc::now().with_timezone(&time_zone);
+    let next = match action {
+        "snooze" => argument
+            .parse()
+            .ok()
+            .map(|minutes| now.with_timezone(&Utc) + chrono::Duration::minutes(minutes)),
+        "tomorrow" => parse_time("tomorrow", now),
+        "done" => None,
+        _ => return Err(DiscordApiError(ERR_CMD_ARGS_INVALID.to_string())),
+    };
+
+    let status = match next {
+        Some(next) => {
+            // Get guild, channel, message and user ids
+            let (guild_db_id, channel_db_id, message_db_id, user_db_id) = get_reminder_ids(
+                &database,
+                component.guild_id,
+                component.channel_id,
+                component.message.id,
+                component.user.id,
+            )
+            .await?;
+
+            database
+                .client
+                .execute(
+                    "
+                    INSERT INTO reminders (guild, channel, message, \"user\", time, content)
+                    VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, $4::BIGINT, $5::TIMESTAMPTZ,
+                        $6::TEXT)
+                    ",
+                    &[
+                        &guild_db_id,
+                        &channel_db_id,
+                        &message_db_id,
+                        &user_db_id,
+                        &next,
+                        &content,
+                    ],
+                )
                 .await?;
+            scheduler.schedule(next).await;
+
+            format!("I'm going to remind you again <t:{}:R>.", next.timestamp())
         }
+        None => "Done!".to_string(),
+    };
+
+    // Replace the buttons with the status of the reminder
+    let embed = create_embed("Reminder", &format!("{}\n\n*{}*", content, status));
+    component
+        .create_interaction_response(&ctx.http, |response| {
+            response
+                .kind(InteractionResponseType::UpdateMessage)
+                .interaction_response_data(|data| {
+                    data.set_embeds(vec![embed])
+                        .components(|components| components)
+                })
+        })
+        .await?;
+
+    Ok(())
+}
+
+fn reminder_buttons(user_id: UserId) -> CreateActionRow {
+    let mut row = CreateActionRow::default();
+
+    for (minutes, label) in SNOOZE_MINUTES {
+        row.create_button(|button| {
+            button
+                .label(format!("Snooze {}", label))
+                .custom_id(format!("{}:snooze:{}:{}", REMINDER_ID, minutes, user_id.0))
+                .style(ButtonStyle::Secondary)
+        });
     }
 
+    row.create_button(|button| {
+        button
+            .label("Tomorrow")
+            .custom_id(format!("{}:tomorrow:-:{}", REMINDER_ID, user_id.0))
+            .style(ButtonStyle::Secondary)
+    })
+    .create_button(|button| {
+        button
+            .label("Done")
+            .custom_id(format!("{}:done:-:{}", REMINDER_ID, user_id.0))
+            .style(ButtonStyle::Success)
+    });
+
+    row
+}
+
+async fn respond(
+    ctx: &Context,
+    component: &MessageComponentInteraction,
+    content: &str,
+) -> Result<(), KowalskiError> {
+    let embed = create_embed("Reminder", content);
+
+    component
+        .create_interaction_response(&ctx.http, |response| {
+            response
+                .kind(InteractionResponseType::ChannelMessageWithSource)
+                .interaction_response_data(|data| data.ephemeral(true).add_embed(embed))
+        })
+        .await?;
+
     Ok(())
 }
@@ -0,0 +1,413 @@
+use itertools::Itertools;
+use serenity::{
+    builder::CreateActionRow,
+    client::Context,
+    model::{
+        id::{ChannelId, GuildId, MessageId, RoleId},
+        interactions::{
+            message_component::{ButtonStyle, MessageComponentInteraction},
+            InteractionResponseType,
+        },
+    },
+    prelude::Mentionable,
+};
+use tracing::error;
+
+use crate::{
+    data,
+    database::{client::Database, types::ModuleStatus},
+    error::KowalskiError,
+    role_requirements::check_requirements,
+    strings::ERR_MESSAGE_COMPONENT,
+    utils::create_embed,
+};
+
+/// Prefix of the custom ids of all role-menu components.
+pub const ROLE_MENU_ID: &str = "rolemenu";
+
+/// Maximum number of roles a single menu can hold.
+pub const ROLE_MENU_SIZE: usize = 25;
+
+/// Update the message of a role menu to reflect its current roles.
+///
+/// Note: If the menu has no roles left, its message gets deleted.
+pub async fn update_menu(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    name: &str,
+) -> Result<(), KowalskiError> {
+    // Get guild id
+    let guild_db_id = database.get_guild(guild_id).await?;
+
+    // Get the menu
+    let (channel_id, message_id, kind) = {
+        let row = database
+            .client
+            .query_one(
+                "
+                SELECT channel, message, kind FROM role_menus
+                WHERE guild = $1::BIGINT AND name = $2::TEXT
+                ",
+                &[&guild_db_id, &name],
+            )
+            .await?;
+
+        (
+            ChannelId(row.get::<_, i64>(0) as u64),
+            MessageId(row.get::<_, i64>(1) as u64),
+            row.get::<_, String>(2),
+        )
+    };
+
+    // Get the roles of the menu
+    let roles: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT role FROM role_menu_roles
+                WHERE guild = $1::BIGINT AND name = $2::TEXT
+                ORDER BY role
+                ",
+                &[&guild_db_id, &name],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                let role_id = RoleId(row.get::<_, i64>(0) as u64);
+                let label = role_id
+                    .to_role_cached(ctx)
+                    .map_or("Unknown role".to_string(), |role| role.name);
+
+                (role_id, label.chars().take(80).collect::<String>())
+            })
+            .collect()
+    };
+
+    if roles.is_empty() {
+        database
+            .client
+            .execute(
+                "
+                DELETE FROM role_menus
+                WHERE guild = $1::BIGINT AND name = $2::TEXT
+                ",
+                &[&guild_db_id, &name],
+            )
+            .await?;
+
+        channel_id.delete_message(&ctx.http, message_id).await?;
+
+        return Ok(());
+    }
+
+    // Create the components
+    let mut rows = Vec::new();
+    if kind == "select" {
+        let mut row = CreateActionRow::default();
+        row.create_select_menu(|menu| {
+            menu.custom_id(ROLE_MENU_ID)
+                .placeholder("Select your roles")
+                .min_values(0)
+                .max_values(roles.len() as u64)
+                .options(|options| {
+                    for (role_id, label) in &roles {
+                        options.create_option(|option| {
+                            option.label(label).value(role_id.0.to_string())
+                        });
+                    }
+                    options
+                })
+        });
+        rows.push(row);
+    } else {
+        for chunk in &roles.iter().chunks(5) {
+            let mut row = CreateActionRow::default();
+            for (role_id, label) in chunk {
+                row.create_button(|button| {
+                    button
+                        .label(label)
+                        .custom_id(format!("{}:{}", ROLE_MENU_ID, role_id.0))
+                        .style(ButtonStyle::Secondary)
+                });
+            }
+            rows.push(row);
+        }
+    }
+
+    let embed = create_embed(
+        name,
+        &format!(
+            "{}\n\n{}",
+            if kind == "select" {
+                "Select the roles you want to hold."
+            } else {
+                "Click a button to pick or drop the role."
+            },
+            roles
+                .iter()
+                .map(|(role_id, _)| role_id.mention())
+                .join("\n")
+        ),
+    );
+
+    channel_id
+        .edit_message(&ctx.http, message_id, |message| {
+            message
+                .set_embeds(vec![embed])
+                .components(|components| components.set_action_rows(rows))
+        })
+        .await?;
+
+    Ok(())
+}
+
+/// Assign or remove the roles a user has picked in a role menu.
+pub async fn answer_role_menu(
+    ctx: &Context,
+    component: &MessageComponentInteraction,
+) -> Result<(), KowalskiError> {
+    // Acknowledge the interaction before updating the roles
+    component
+        .create_interaction_response(&ctx.http, |response| {
+            response
+                .kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                .interaction_response_data(|data| data.ephemeral(true))
+        })
+        .await?;
+
+    // Get database
+    let database = data!(ctx, Database);
+
+    let (guild_id, mut member) = match (component.guild_id, component.member.clone()) {
+        (Some(guild_id), Some(member)) if !member.user.bot => (guild_id, member),
+        _ => return respond(ctx, component, "Role menu", "Roles can't be assigned here.").await,
+    };
+
+    // Get guild, channel and message ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let channel_db_id = component.channel_id.0 as i64;
+    let message_db_id = component.message.id.0 as i64;
+
+    // Check whether the module is enabled
+    let status = database
+        .client
+        .query_opt(
+            "
+            SELECT status
+            FROM modules
+            WHERE guild = $1::BIGINT
+            ",
+            &[&guild_db_id],
+        )
+        .await?
+        .map_or(ModuleStatus::default(), |row| row.get(0));
+
+    // Get the menu and its roles
+    let menu = database
+        .client
+        .query_opt(
+            "
+            SELECT name FROM role_menus
+            WHERE guild = $1::BIGINT AND channel = $2::BIGINT AND message = $3::BIGINT
+            ",
+            &[&guild_db_id, &channel_db_id, &message_db_id],
+        )
+        .await?
+        .map(|row| row.get::<_, String>(0));
+
+    let name = match menu {
+        Some(name) if status.reaction_roles => name,
+        _ => {
+            return respond(
+                ctx,
+                component,
+                "Role menu",
+                "This role menu is no longer available.",
+            )
+            .await
+        }
+    };
+
+    let menu_roles: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT role FROM role_menu_roles
+                WHERE guild = $1::BIGINT AND name = $2::TEXT
+                ",
+                &[&guild_db_id, &name],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| RoleId(row.get::<_, i64>(0) as u64))
+            .collect()
+    };
+
+    // Get the roles the user wants to hold after the interaction
+    let wanted: Vec<_> = if component.data.custom_id == ROLE_MENU_ID {
+        component
+            .data
+            .values
+            .iter()
+            .filter_map(|value| value.parse().ok().map(RoleId))
+            .collect()
+    } else {
+        let role_id = component
+            .data
+            .custom_id
+            .split(':')
+            .nth(1)
+            .and_then(|role| role.parse().ok())
+            .map(RoleId);
+
+        // Clicking a button toggles its role
+        menu_roles
+            .iter()
+            .filter(|role| {
+                let held = member.roles.contains(role);
+                if Some(**role) == role_id {
+                    !held
+                } else {
+                    held
+                }
+            })
+            .copied()
+            .collect()
+    };
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut full = Vec::new();
+    let mut rejected = Vec::new();
+    let mut failed = Vec::new();
+
+    for role in menu_roles {
+        let held = member.roles.contains(&role);
+
+        if wanted.contains(&role) && !held {
+            // Check whether the user is allowed to pick the role
+            if let Some(reason) = check_requirements(&database, guild_id, &member, role).await? {
+                rejected.push(reason);
+                continue;
+            }
+
+            // Take a slot if there are any left
+            let available = database
+                .client
+                .query_opt(
+                    "
+                    UPDATE role_menu_roles
+                    SET slots = slots - 1
+                    WHERE guild = $1::BIGINT AND name = $2::TEXT AND role = $3::BIGINT
+                    AND (slots IS NULL OR slots > 0)
+                    RETURNING role
+                    ",
+                    &[&guild_db_id, &name, &(role.0 as i64)],
+                )
+                .await?
+                .is_some();
+
+            if !available {
+                full.push(role);
+                continue;
+            }
+
+            match member.add_role(&ctx.http, role).await {
+                Ok(()) => added.push(role),
+                Err(why) => {
+                    error!("{}: {:?}", ERR_MESSAGE_COMPONENT, why);
+
+                    // Give the slot back
+                    free_slot(&database, guild_db_id, &name, role).await?;
+                    failed.push(role);
+                }
+            }
+        } else if !wanted.contains(&role) && held {
+            match member.remove_role(&ctx.http, role).await {
+                Ok(()) => {
+                    // Free the slot again
+                    free_slot(&database, guild_db_id, &name, role).await?;
+                    removed.push(role);
+                }
+                Err(why) => {
+                    error!("{}: {:?}", ERR_MESSAGE_COMPONENT, why);
+                    failed.push(role);
+                }
+            }
+        }
+    }
+
+    let mut content = Vec::new();
+    if !added.is_empty() {
+        content.push(format!(
+            "You have received {}.",
+            added.iter().map(|role| role.mention()).join(", ")
+        ));
+    }
+    if !removed.is_empty() {
+        content.push(format!(
+            "You no longer hold {}.",
+            removed.iter().map(|role| role.mention()).join(", ")
+        ));
+    }
+    if !full.is_empty() {
+        content.push(format!(
+            "There are no slots left for {}.",
+            full.iter().map(|role| role.mention()).join(", ")
+        ));
+    }
+    content.append(&mut rejected);
+    if !failed.is_empty() {
+        content.push(format!(
+            "I couldn't update {}, please try again later.",
+            failed.iter().map(|role| role.mention()).join(", ")
+        ));
+    }
+    if content.is_empty() {
+        content.push("Your roles haven't changed.".to_string());
+    }
+
+    respond(ctx, component, &name, &content.join("\n")).await
+}
+
+async fn free_slot(
+    database: &Database,
+    guild_db_id: i64,
+    name: &str,
+    role: RoleId,
+) -> Result<(), KowalskiError> {
+    database
+        .client
+        .execute(
+            "
+            UPDATE role_menu_roles
+            SET slots = slots + 1
+            WHERE guild = $1::BIGINT AND name = $2::TEXT AND role = $3::BIGINT
+            AND slots IS NOT NULL
+            ",
+            &[&guild_db_id, &name, &(role.0 as i64)],
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn respond(
+    ctx: &Context,
+    component: &MessageComponentInteraction,
+    title: &str,
+    content: &str,
+) -> Result<(), KowalskiError> {
+    let embed = create_embed(title, content);
+
+    component
+        .edit_original_interaction_response(&ctx.http, |response| response.add_embed(embed))
+        .await?;
+
+    Ok(())
+}
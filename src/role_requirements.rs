@@ -0,0 +1,77 @@
+use itertools::Itertools;
+use serenity::{
+    model::{
+        guild::Member,
+        id::{GuildId, RoleId},
+    },
+    prelude::Mentionable,
+};
+
+use crate::{database::client::Database, error::KowalskiError};
+
+/// Check whether a member is allowed to pick a self-assignable role.
+///
+/// Returns an explanation for the member if the role can't be picked.
+pub async fn check_requirements(
+    database: &Database,
+    guild_id: GuildId,
+    member: &Member,
+    role_id: RoleId,
+) -> Result<Option<String>, KowalskiError> {
+    // Get guild and role ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let role_db_id = database.get_role(guild_id, role_id).await?;
+
+    // Get the required and forbidden roles
+    let requirements: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT requirement, forbidden FROM reaction_role_requirements
+                WHERE guild = $1::BIGINT AND role = $2::BIGINT
+                ",
+                &[&guild_db_id, &role_db_id],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| (RoleId(row.get::<_, i64>(0) as u64), row.get::<_, bool>(1)))
+            .collect()
+    };
+
+    let missing: Vec<_> = requirements
+        .iter()
+        .filter(|(requirement, forbidden)| !forbidden && !member.roles.contains(requirement))
+        .map(|(requirement, _)| requirement.mention())
+        .collect();
+    let forbidden: Vec<_> = requirements
+        .iter()
+        .filter(|(requirement, forbidden)| *forbidden && member.roles.contains(requirement))
+        .map(|(requirement, _)| requirement.mention())
+        .collect();
+
+    let mut reasons = Vec::new();
+    if !missing.is_empty() {
+        reasons.push(format!(
+            "you need to hold {} first",
+            missing.iter().join(", ")
+        ));
+    }
+    if !forbidden.is_empty() {
+        reasons.push(format!(
+            "it can't be picked while holding {}",
+            forbidden.iter().join(", ")
+        ));
+    }
+
+    if reasons.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(format!(
+            "You can't pick the role {}: {}.",
+            role_id.mention(),
+            reasons.join(" and ")
+        )))
+    }
+}
@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use serenity::{
+    client::Context,
+    model::{
+        guild::{ScheduledEvent, ScheduledEventStatus},
+        id::{GuildId, ScheduledEventId},
+    },
+};
+use tracing::error;
+
+use crate::{
+    data, database::client::Database, error::KowalskiError,
+    event_reminders::EventReminderScheduler, strings::ERR_SCHEDULED_EVENT,
+};
+
+/// Number of days finished events are kept after their end.
+const RETENTION_DAYS: i32 = 30;
+
+pub fn cache_scheduled_events(ctx: Context, guild_ids: Vec<GuildId>) {
+    tokio::spawn(async move {
+        // Get database and event reminder scheduler
+        let (database, scheduler) = data!(ctx, (Database, EventReminderScheduler));
+
+        for guild_id in guild_ids {
+            if let Err(why) = reconcile_guild(&ctx, &database, guild_id).await {
+                error!("{}: {:?}", ERR_SCHEDULED_EVENT, why);
+            }
+        }
+
+        // Events might have changed while we were offline
+        scheduler.reschedule();
+    });
+}
+
+// Bring the cached events of a guild in line with the ones known to Discord
+async fn reconcile_guild(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+) -> Result<(), KowalskiError> {
+    let events = guild_id.scheduled_events(&ctx.http, false).await?;
+
+    for event in &events {
+        cache_event(database, event).await?;
+    }
+
+    // Events which vanished while we were offline either ended or got cancelled
+    let event_ids: Vec<i64> = events.iter().map(|event| event.id.0 as i64).collect();
+    database
+        .client
+        .execute(
+            "
+            UPDATE scheduled_events
+            SET status = CASE WHEN start <= NOW() THEN $3::INT ELSE $4::INT END,
+                sequence = sequence + 1, modified = NOW()
+            WHERE guild = $1::BIGINT AND NOT event = ANY($2::BIGINT[])
+                AND status <> ALL(ARRAY[$3::INT, $4::INT])
+            ",
+            &[
+                &(guild_id.0 as i64),
+                &event_ids,
+                &(ScheduledEventStatus::Completed as i32),
+                &(ScheduledEventStatus::Canceled as i32),
+            ],
+        )
+        .await?;
+
+    // Forget about events which are over for a while
+    let deleted = database
+        .client
+        .execute(
+            "
+            DELETE FROM scheduled_events
+            WHERE guild = $1::BIGINT AND status = ANY(ARRAY[$2::INT, $3::INT])
+                AND COALESCE(\"end\", start) < NOW() - make_interval(days => $4::INT)
+            ",
+            &[
+                &(guild_id.0 as i64),
+                &(ScheduledEventStatus::Completed as i32),
+                &(ScheduledEventStatus::Canceled as i32),
+                &RETENTION_DAYS,
+            ],
+        )
+        .await?;
+
+    // Mark the feeds of the guild as changed, as deleted events leave no modification time behind
+    if deleted > 0 {
+        database
+            .client
+            .execute(
+                "UPDATE publishing SET modified = NOW() WHERE guild = $1::BIGINT",
+                &[&(guild_id.0 as i64)],
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Adds an event to the cache or updates it, increasing its sequence number if it changed.
+pub async fn cache_event(database: &Database, event: &ScheduledEvent) -> Result<(), KowalskiError> {
+    let guild_db_id = database.get_guild(event.guild_id).await?;
+    let created: DateTime<Utc> = *event.id.created_at();
+    let end: Option<DateTime<Utc>> = event.end_time.map(|time| *time);
+
+    database
+        .client
+        .execute(
+            "
+            INSERT INTO scheduled_events (guild, event, name, description, channel, location,
+                creator, creator_name, start, \"end\", status, modified)
+            VALUES ($1::BIGINT, $2::BIGINT, $3::TEXT, $4::TEXT, $5::BIGINT, $6::TEXT, $7::BIGINT,
+                $8::TEXT, $9::TIMESTAMPTZ, $10::TIMESTAMPTZ, $11::INT, $12::TIMESTAMPTZ)
+            ON CONFLICT (guild, event) DO UPDATE SET
+                name = EXCLUDED.name, description = EXCLUDED.description,
+                channel = EXCLUDED.channel, location = EXCLUDED.location,
+                creator = EXCLUDED.creator, creator_name = EXCLUDED.creator_name,
+                start = EXCLUDED.start, \"end\" = EXCLUDED.\"end\", status = EXCLUDED.status,
+                sequence = scheduled_events.sequence + 1, modified = NOW()
+            WHERE (scheduled_events.name, scheduled_events.description, scheduled_events.channel,
+                scheduled_events.location, scheduled_events.start, scheduled_events.\"end\",
+                scheduled_events.status)
+                IS DISTINCT FROM (EXCLUDED.name, EXCLUDED.description, EXCLUDED.channel,
+                EXCLUDED.location, EXCLUDED.start, EXCLUDED.\"end\", EXCLUDED.status)
+            ",
+            &[
+                &guild_db_id,
+                &(event.id.0 as i64),
+                &event.name,
+                &event.description,
+                &event.channel_id.map(|channel_id| channel_id.0 as i64),
+                &event
+                    .metadata
+                    .as_ref()
+                    .map(|metadata| metadata.location.clone()),
+                &event.creator.as_ref().map(|creator| creator.id.0 as i64),
+                &event.creator.as_ref().map(|creator| creator.name.clone()),
+                &*event.start_time,
+                &end,
+                &(event.status as i32),
+                &created,
+            ],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Marks a cached event as cancelled unless it already ended, so calendar clients remove it.
+pub async fn uncache_event(
+    database: &Database,
+    guild_id: GuildId,
+    event_id: ScheduledEventId,
+) -> Result<(), KowalskiError> {
+    database
+        .client
+        .execute(
+            "
+            UPDATE scheduled_events
+            SET status = $3::INT, sequence = sequence + 1, modified = NOW()
+            WHERE guild = $1::BIGINT AND event = $2::BIGINT
+                AND status <> ALL(ARRAY[$3::INT, $4::INT])
+            ",
+            &[
+                &(guild_id.0 as i64),
+                &(event_id.0 as i64),
+                &(ScheduledEventStatus::Canceled as i32),
+                &(ScheduledEventStatus::Completed as i32),
+            ],
+        )
+        .await?;
+
+    Ok(())
+}
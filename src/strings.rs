@@ -5,6 +5,7 @@ pub const INFO_CONNECTED: &str = "Connection to Discord API established!";
 pub const INFO_DB_CONNECTED: &str = "Database connection established.";
 pub const INFO_DB_SETUP: &str = "Database setup complete.";
 // Error messages
+pub const ERR_ACHIEVEMENTS: &str = "Failed to check the achievements";
 pub const ERR_API_LOAD: &str = "Failed to request information from the REST API";
 pub const ERR_AUTOCOMPLETE: &str = "Failed to answer the autocomplete request";
 pub const ERR_CALENDAR: &str = "Failed to host the calendar";
@@ -26,12 +27,21 @@ pub const ERR_DATA_ACCESS: &str = "Failed to access the global data";
 pub const ERR_DB_CONNECTION: &str = "Database connection error";
 pub const ERR_DB_QUERY: &str = "Failed to execute the database query";
 pub const ERR_ENV_NOT_SET: &str = "Environment variable not set";
+pub const ERR_EVENT_REMINDER: &str = "Failed to send the event reminders";
+pub const ERR_GUILD_NOT_CACHED: &str = "Failed to find the guild in the cache";
 pub const ERR_MEMBER_REMOVAL: &str = "Failed to handle the member removal event";
+pub const ERR_MESSAGE: &str = "Failed to handle the message event";
 pub const ERR_MESSAGE_COMPONENT: &str = "Failed to answer the message component request";
 pub const ERR_MODEL_CREATE: &str = "Failed to create the model";
 pub const ERR_REACTION: &str = "Failed to handle the reaction event";
+pub const ERR_REACTION_SLOTS: &str = "Failed to reconcile the reaction-role slots";
 pub const ERR_REMINDER: &str = "Failed to send the reminder";
+pub const ERR_SCHEDULED_EVENT: &str = "Failed to cache the scheduled events";
+pub const ERR_TEMPORARY_ROLE: &str = "Failed to remove the temporary role";
+pub const ERR_VOICE: &str = "Failed to track the voice activity";
 // User error messages
 pub const ERR_USER_TITLE: &str = "Looks like something really went wrong here :/";
 pub const ERR_USER_EXECUTION_FAILED: &str =
     "You may want to reach out to the owner of this bot to check what went wrong.";
+pub const ERR_USER_REMINDERS_DISABLED: &str =
+    "Reminders are disabled on this guild. Try sending me a direct message instead!";
@@ -0,0 +1,178 @@
+use std::time::Duration;
+
+use serenity::{
+    client::Context,
+    model::id::{GuildId, RoleId, UserId},
+};
+use tokio::time::interval;
+use tracing::error;
+
+use crate::{
+    data, database::client::Database, error::KowalskiError, reaction_slots::fill_waitlist,
+    strings::ERR_TEMPORARY_ROLE,
+};
+
+pub fn check_temporary_roles(ctx: Context, period: Duration) {
+    tokio::spawn(async move {
+        // Get database
+        let database = data!(ctx, Database);
+
+        // Create the interval at which we will check for expired roles
+        let mut interval = interval(period);
+
+        loop {
+            // Wait for the next tick
+            interval.tick().await;
+
+            if let Err(why) = remove_expired_roles(&ctx, &database).await {
+                error!("{}: {:?}", ERR_TEMPORARY_ROLE, why);
+            }
+        }
+    });
+}
+
+/// Schedule the removal of a role after the given amount of hours.
+///
+/// Note: If the role is already scheduled for removal, the duration will be added on top.
+pub async fn add_temporary_role(
+    database: &Database,
+    guild_id: GuildId,
+    user_id: UserId,
+    role_id: RoleId,
+    hours: i64,
+) -> Result<(), KowalskiError> {
+    // Get guild, user and role ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let user_db_id = database.get_user(guild_id, user_id).await?;
+    let role_db_id = database.get_role(guild_id, role_id).await?;
+
+    database
+        .client
+        .execute(
+            "
+            INSERT INTO temporary_roles
+            VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT, NOW() + $4::BIGINT * INTERVAL '1 hour')
+            ON CONFLICT (guild, \"user\", role)
+            DO UPDATE SET expires = GREATEST(temporary_roles.expires, NOW())
+                + $4::BIGINT * INTERVAL '1 hour'
+            ",
+            &[&guild_db_id, &user_db_id, &role_db_id, &hours],
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Cancel the scheduled removal of a role, e.g. because it got removed early.
+pub async fn remove_temporary_role(
+    database: &Database,
+    guild_id: GuildId,
+    user_id: UserId,
+    role_id: RoleId,
+) -> Result<(), KowalskiError> {
+    // Get guild, user and role ids
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let user_db_id = database.get_user(guild_id, user_id).await?;
+    let role_db_id = database.get_role(guild_id, role_id).await?;
+
+    database
+        .client
+        .execute(
+            "
+            DELETE FROM temporary_roles
+            WHERE guild = $1::BIGINT AND \"user\" = $2::BIGINT AND role = $3::BIGINT
+            ",
+            &[&guild_db_id, &user_db_id, &role_db_id],
+        )
+        .await?;
+
+    Ok(())
+}
+
+async fn remove_expired_roles(ctx: &Context, database: &Database) -> Result<(), KowalskiError> {
+    // Get expired roles
+    let roles: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT guild, \"user\", role FROM temporary_roles
+                WHERE expires <= NOW()
+                ",
+                &[],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                (
+                    GuildId(row.get::<_, i64>(0) as u64),
+                    UserId(row.get::<_, i64>(1) as u64),
+                    RoleId(row.get::<_, i64>(2) as u64),
+                )
+            })
+            .collect()
+    };
+
+    for (guild_id, user_id, role_id) in roles {
+        // A single failure (e.g. missing permissions) should not block the other removals
+        if let Err(why) = expire_role(ctx, database, guild_id, user_id, role_id).await {
+            error!("{}: {:?}", ERR_TEMPORARY_ROLE, why);
+        }
+    }
+
+    Ok(())
+}
+
+async fn expire_role(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    user_id: UserId,
+    role_id: RoleId,
+) -> Result<(), KowalskiError> {
+    // Remove the role first, so failed removals are retried on the next tick
+    ctx.http
+        .remove_member_role(
+            guild_id.0,
+            user_id.0,
+            role_id.0,
+            Some("Temporary role expired"),
+        )
+        .await?;
+
+    remove_temporary_role(database, guild_id, user_id, role_id).await?;
+
+    // Release the slots of limited reaction-roles
+    let reaction_roles: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                UPDATE reaction_roles
+                SET slots = slots + 1
+                WHERE guild = $1::BIGINT AND role = $2::BIGINT AND slots IS NOT NULL
+                RETURNING channel, message, emoji
+                ",
+                &[&(guild_id.0 as i64), &(role_id.0 as i64)],
+            )
+            .await?;
+
+        rows.iter()
+            .map(|row| {
+                (
+                    row.get::<_, i64>(0),
+                    row.get::<_, i64>(1),
+                    row.get::<_, i32>(2),
+                )
+            })
+            .collect()
+    };
+
+    // Hand the freed slots to the next users on the waitlists
+    for reaction_role in reaction_roles {
+        fill_waitlist(ctx, database, guild_id, reaction_role, role_id).await?;
+    }
+
+    Ok(())
+}
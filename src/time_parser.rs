@@ -0,0 +1,337 @@
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday,
+};
+
+/// Format used to show points in time in the time zone of a user.
+pub const DATE_FORMAT: &str = "%A, %d %B %Y %H:%M";
+
+/// Response to a time expression which couldn't be parsed, including some examples.
+pub fn time_hint(input: &str) -> String {
+    format!(
+        "I don't understand \"{}\". Try something like \"in 2h30m\", \"tomorrow 9am\", \
+        \"next friday 18:00\", \"2026-12-24 20:00\" or \"at 5pm\".",
+        input
+    )
+}
+
+/// Resolve a natural-language time expression to an absolute point in time.
+///
+/// Supported are relative durations ("in 2h30m", "in 3 days"), days ("today", "tomorrow",
+/// "friday", "next friday", "2026-12-24") and times of day ("9am", "18:00", "at 5pm", "noon"),
+/// as well as combinations of a day and a time ("tomorrow 9am", "next friday 18:00").
+/// Days and times are interpreted in the given time zone.
+///
+/// Returns `None` if the expression couldn't be parsed.
+pub fn parse_time<Tz: TimeZone>(input: &str, now: DateTime<Tz>) -> Option<DateTime<Utc>> {
+    let input = input.trim().to_lowercase();
+
+    // Relative durations
+    let relative = input.strip_prefix("in ").unwrap_or(&input);
+    if let Some(duration) = parse_duration(relative) {
+        return now.with_timezone(&Utc).checked_add_signed(duration);
+    }
+
+    let local = now.naive_local();
+    let tokens = tokenize(&input);
+
+    let mut date = None;
+    let mut time = None;
+    let mut next = false;
+    let mut weekday = None;
+
+    for token in tokens {
+        match token.as_str() {
+            "at" | "on" => {}
+            "next" => next = true,
+            "today" if date.is_none() => date = Some(local.date()),
+            "tomorrow" if date.is_none() => date = Some(local.date().succ_opt()?),
+            _ => {
+                if let Some(day) = parse_weekday(&token).filter(|_| date.is_none()) {
+                    weekday = Some(day);
+                } else if let Some(day) = NaiveDate::parse_from_str(&token, "%Y-%m-%d")
+                    .ok()
+                    .filter(|_| date.is_none() && weekday.is_none())
+                {
+                    date = Some(day);
+                } else if let Some(of_day) = parse_time_of_day(&token).filter(|_| time.is_none()) {
+                    time = Some(of_day);
+                } else {
+                    return None;
+                }
+            }
+        }
+    }
+
+    // "next" is only valid in front of a weekday
+    if next && weekday.is_none() {
+        return None;
+    }
+
+    // Dates without a time refer to the morning
+    let time_of_day = time.unwrap_or_else(|| NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+
+    let datetime = match (date, weekday) {
+        (Some(date), _) => date.and_time(time_of_day),
+        (None, Some(weekday)) => {
+            let mut days = (7 + weekday.num_days_from_monday() as i64
+                - local.weekday().num_days_from_monday() as i64)
+                % 7;
+            if days == 0 && (next || time_of_day <= local.time()) {
+                days = 7;
+            }
+
+            local
+                .date()
+                .checked_add_signed(Duration::days(days))?
+                .and_time(time_of_day)
+        }
+        (None, None) => {
+            let time = time?;
+            let today = local.date().and_time(time);
+
+            // Times which have already passed today refer to tomorrow
+            if today <= local {
+                today.checked_add_signed(Duration::days(1))?
+            } else {
+                today
+            }
+        }
+    };
+
+    resolve(&now.timezone(), datetime)
+}
+
+// Split the input into tokens, attaching a separate am/pm to the preceding number
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+
+    for token in input.split(|c: char| c.is_whitespace() || c == ',') {
+        if token.is_empty() {
+            continue;
+        }
+
+        match (token, tokens.last_mut()) {
+            ("am" | "pm", Some(last)) if last.chars().all(|c| c.is_ascii_digit() || c == ':') => {
+                last.push_str(token)
+            }
+            _ => tokens.push(token.to_string()),
+        }
+    }
+
+    tokens
+}
+
+/// Parse a duration like "2h30m" or "1 day and 2 hours".
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    // Split the input into runs of digits and runs of letters
+    let mut runs: Vec<String> = Vec::new();
+    let mut previous = None;
+    for c in input.chars() {
+        let digit = if c.is_ascii_digit() {
+            true
+        } else if c.is_alphabetic() {
+            false
+        } else if c.is_whitespace() || c == ',' {
+            previous = None;
+            continue;
+        } else {
+            return None;
+        };
+
+        match runs.last_mut() {
+            Some(run) if previous == Some(digit) => run.push(c),
+            _ => runs.push(c.to_string()),
+        }
+        previous = Some(digit);
+    }
+
+    // Allow connecting words between the components
+    runs.retain(|run| run != "and");
+
+    if runs.is_empty() || !runs.len().is_multiple_of(2) {
+        return None;
+    }
+
+    runs.chunks(2).try_fold(Duration::zero(), |total, pair| {
+        total.checked_add(&duration_of(&pair[0], &pair[1])?)
+    })
+}
+
+fn duration_of(number: &str, unit: &str) -> Option<Duration> {
+    let amount: i64 = number.parse().ok()?;
+
+    match unit {
+        "s" | "sec" | "secs" | "second" | "seconds" => Duration::try_seconds(amount),
+        "m" | "min" | "mins" | "minute" | "minutes" => Duration::try_minutes(amount),
+        "h" | "hr" | "hrs" | "hour" | "hours" => Duration::try_hours(amount),
+        "d" | "day" | "days" => Duration::try_days(amount),
+        "w" | "week" | "weeks" => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+fn parse_weekday(input: &str) -> Option<Weekday> {
+    match input {
+        "monday" | "mon" => Some(Weekday::Mon),
+        "tuesday" | "tue" | "tues" => Some(Weekday::Tue),
+        "wednesday" | "wed" => Some(Weekday::Wed),
+        "thursday" | "thu" | "thurs" => Some(Weekday::Thu),
+        "friday" | "fri" => Some(Weekday::Fri),
+        "saturday" | "sat" => Some(Weekday::Sat),
+        "sunday" | "sun" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_time_of_day(input: &str) -> Option<NaiveTime> {
+    match input {
+        "noon" => return NaiveTime::from_hms_opt(12, 0, 0),
+        "midnight" => return NaiveTime::from_hms_opt(0, 0, 0),
+        _ => {}
+    }
+
+    // Get the 12-hour clock suffix
+    let (clock, offset) = match (input.strip_suffix("am"), input.strip_suffix("pm")) {
+        (Some(clock), _) => (clock, Some(0)),
+        (_, Some(clock)) => (clock, Some(12)),
+        _ => (input, None),
+    };
+
+    let (hours, minutes) = match clock.split_once(':') {
+        Some((hours, minutes)) if minutes.len() == 2 => (hours, minutes),
+        Some(_) => return None,
+        // A plain number is only a time when used with am or pm
+        None if offset.is_some() => (clock, "0"),
+        None => return None,
+    };
+
+    let mut hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+
+    if let Some(offset) = offset {
+        if !(1..=12).contains(&hours) {
+            return None;
+        }
+        hours = hours % 12 + offset;
+    }
+
+    NaiveTime::from_hms_opt(hours, minutes, 0)
+}
+
+/// Convert a local time to UTC, skipping over gaps caused by daylight saving time.
+pub fn resolve<Tz: TimeZone>(tz: &Tz, datetime: NaiveDateTime) -> Option<DateTime<Utc>> {
+    tz.from_local_datetime(&datetime)
+        .earliest()
+        .or_else(|| {
+            tz.from_local_datetime(&datetime.checked_add_signed(Duration::hours(1))?)
+                .earliest()
+        })
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use chrono_tz::{Europe::Berlin, Tz};
+
+    use super::{parse_duration, parse_time};
+
+    // Wednesday, 25 March 2026 12:00 in Berlin, a few days before daylight saving time starts
+    fn now() -> DateTime<Tz> {
+        Berlin.with_ymd_and_hms(2026, 3, 25, 12, 0, 0).unwrap()
+    }
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> Option<DateTime<Utc>> {
+        Utc.with_ymd_and_hms(year, month, day, hour, minute, 0)
+            .single()
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(
+            parse_duration("2h30m"),
+            Some(Duration::hours(2) + Duration::minutes(30))
+        );
+        assert_eq!(
+            parse_duration("1 day and 2 hours"),
+            Some(Duration::days(1) + Duration::hours(2))
+        );
+        assert_eq!(parse_duration("3 weeks"), Some(Duration::weeks(3)));
+        assert_eq!(parse_duration("2h30"), None);
+        assert_eq!(parse_duration("2 fortnights"), None);
+        assert_eq!(parse_duration("-2h"), None);
+    }
+
+    #[test]
+    fn relative_times() {
+        assert_eq!(parse_time("in 2h30m", now()), utc(2026, 3, 25, 13, 30));
+        assert_eq!(parse_time("3 days", now()), utc(2026, 3, 28, 11, 0));
+    }
+
+    #[test]
+    fn absolute_times() {
+        assert_eq!(
+            parse_time("2026-12-24 20:00", now()),
+            utc(2026, 12, 24, 19, 0)
+        );
+        assert_eq!(parse_time("2026-12-24", now()), utc(2026, 12, 24, 8, 0));
+        assert_eq!(parse_time("at 5pm", now()), utc(2026, 3, 25, 16, 0));
+        // Times which have already passed refer to the next day
+        assert_eq!(parse_time("9am", now()), utc(2026, 3, 26, 8, 0));
+        assert_eq!(parse_time("noon", now()), utc(2026, 3, 26, 11, 0));
+    }
+
+    #[test]
+    fn days() {
+        assert_eq!(parse_time("tomorrow 9am", now()), utc(2026, 3, 26, 8, 0));
+        assert_eq!(parse_time("tomorrow", now()), utc(2026, 3, 26, 8, 0));
+        assert_eq!(parse_time("today 18:00", now()), utc(2026, 3, 25, 17, 0));
+        assert_eq!(parse_time("friday", now()), utc(2026, 3, 27, 8, 0));
+        assert_eq!(
+            parse_time("next friday 18:00", now()),
+            utc(2026, 3, 27, 17, 0)
+        );
+        // The current weekday refers to the next week if the time has passed or "next" is given
+        assert_eq!(parse_time("wednesday 9am", now()), utc(2026, 4, 1, 7, 0));
+        assert_eq!(
+            parse_time("wednesday 18:00", now()),
+            utc(2026, 3, 25, 17, 0)
+        );
+        assert_eq!(
+            parse_time("next wednesday 18:00", now()),
+            utc(2026, 4, 1, 16, 0)
+        );
+    }
+
+    #[test]
+    fn daylight_saving_time() {
+        // 02:30 doesn't exist on the 29th of March in Berlin, so the time is moved past the gap
+        assert_eq!(
+            parse_time("2026-03-29 02:30", now()),
+            utc(2026, 3, 29, 1, 30)
+        );
+        assert_eq!(
+            parse_time("2026-03-29 04:00", now()),
+            utc(2026, 3, 29, 2, 0)
+        );
+    }
+
+    #[test]
+    fn invalid_times() {
+        assert_eq!(parse_time("", now()), None);
+        assert_eq!(parse_time("someday", now()), None);
+        assert_eq!(parse_time("next 5pm", now()), None);
+        assert_eq!(parse_time("13pm", now()), None);
+        assert_eq!(parse_time("25:00", now()), None);
+        assert_eq!(parse_time("2026-02-30", now()), None);
+    }
+
+    #[test]
+    fn overflows() {
+        assert_eq!(parse_duration("9223372036854775807s"), None);
+        assert_eq!(parse_duration("99999999999999999999 days"), None);
+        assert_eq!(parse_duration("9000000000000000s 9000000000000000s"), None);
+        assert_eq!(parse_time("in 100000000 weeks", now()), None);
+        assert_eq!(parse_time("in 9223372036854775 seconds", now()), None);
+    }
+}
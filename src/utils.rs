@@ -29,7 +29,7 @@ use serenity::{
 use tracing::error;
 
 use crate::{
-    config::{Command, CommandOption, Config, Module, Value},
+    config::{Command, CommandKind, CommandOption, Config, Module, Value},
     database::types::ModuleStatus,
     error::KowalskiError,
     error::KowalskiError::DiscordApiError,
@@ -275,13 +275,21 @@ async fn edit_embed(
 /// Create a general command
 pub fn create_command(name: &str, command_config: &Command) -> CreateApplicationCommand {
     let mut command = CreateApplicationCommand::default();
+    let kind = command_config.kind.unwrap_or(CommandKind::Slash);
 
-    command.name(name).description(&command_config.description);
+    command.name(name).kind(kind.into());
 
     if let Some(permission) = command_config.permission {
         command.default_member_permissions(permission);
     }
 
+    // Context menu commands must not have a description or options
+    if kind != CommandKind::Slash {
+        return command;
+    }
+
+    command.description(&command_config.description);
+
     // Add options if there are any
     if let Some(options) = &command_config.options {
         let (required, unrequired): (
@@ -325,6 +333,8 @@ pub async fn create_module_command(
                 Module::Score => status.score,
                 Module::ReactionRoles => status.reaction_roles,
                 Module::Analyze => status.analyze,
+                Module::Voice => status.voice,
+                Module::Economy => status.economy,
             },
             None => false,
         });
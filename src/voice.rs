@@ -0,0 +1,164 @@
+use std::{collections::HashMap, time::Duration};
+
+use serenity::{
+    client::Context,
+    model::id::{ChannelId, GuildId, UserId},
+};
+use tokio::time::interval;
+use tracing::error;
+
+use crate::{
+    data,
+    database::{client::Database, types::ModuleStatus},
+    error::KowalskiError,
+    events::reaction::update_roles,
+    strings::ERR_VOICE,
+};
+
+pub fn check_voice(ctx: Context, period: Duration) {
+    tokio::spawn(async move {
+        // Get database
+        let database = data!(ctx, Database);
+
+        // Create the interval at which we will check the voice channels
+        let mut interval = interval(period);
+
+        // Amount of minutes to credit per tick
+        let minutes = (period.as_secs() / 60) as i64;
+
+        // The first tick completes immediately, only credit users after a full period
+        interval.tick().await;
+
+        loop {
+            // Wait for the next tick
+            interval.tick().await;
+
+            if let Err(why) = track_voice(&ctx, &database, minutes).await {
+                error!("{}: {:?}", ERR_VOICE, why);
+            }
+        }
+    });
+}
+
+async fn track_voice(
+    ctx: &Context,
+    database: &Database,
+    minutes: i64,
+) -> Result<(), KowalskiError> {
+    // Get guilds which have the voice module enabled and a conversion rate set
+    let guilds: Vec<_> = {
+        let rows = database
+            .client
+            .query(
+                "
+                SELECT m.guild, status, minutes
+                FROM modules m
+                INNER JOIN voice_rates vr ON m.guild = vr.guild
+                ",
+                &[],
+            )
+            .await?;
+
+        rows.iter()
+            .filter(|row| row.get::<_, ModuleStatus>(1).voice)
+            .map(|row| (GuildId(row.get::<_, i64>(0) as u64), row.get::<_, i64>(2)))
+            .collect()
+    };
+
+    for (guild_id, rate) in guilds {
+        let guild = match guild_id.to_guild_cached(&ctx.cache) {
+            Some(guild) => guild,
+            None => continue,
+        };
+
+        // Group the unmuted humans by their voice channel
+        let mut channels: HashMap<ChannelId, Vec<UserId>> = HashMap::new();
+
+        for (user_id, voice_state) in &guild.voice_states {
+            let channel_id = match voice_state.channel_id {
+                Some(channel_id) => channel_id,
+                None => continue,
+            };
+
+            // Ignore the AFK channel
+            if guild.afk_channel_id == Some(channel_id) {
+                continue;
+            }
+
+            // Ignore muted users
+            if voice_state.mute || voice_state.self_mute {
+                continue;
+            }
+
+            // Ignore bots
+            let bot = match guild.members.get(user_id) {
+                Some(member) => member.user.bot,
+                None => voice_state
+                    .member
+                    .as_ref()
+                    .is_some_and(|member| member.user.bot),
+            };
+            if bot {
+                continue;
+            }
+
+            channels.entry(channel_id).or_default().push(*user_id);
+        }
+
+        // Only conversations of at least two users count
+        let active = channels
+            .into_values()
+            .filter(|users| users.len() >= 2)
+            .flatten();
+
+        for user_id in active {
+            // A single user failing shouldn't keep the others from being credited
+            if let Err(why) = credit_user(ctx, database, guild_id, user_id, minutes, rate).await {
+                error!("{}: {:?}", ERR_VOICE, why);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn credit_user(
+    ctx: &Context,
+    database: &Database,
+    guild_id: GuildId,
+    user_id: UserId,
+    minutes: i64,
+    rate: i64,
+) -> Result<(), KowalskiError> {
+    let guild_db_id = database.get_guild(guild_id).await?;
+    let user_db_id = database.get_user(guild_id, user_id).await?;
+
+    // Credit the voice activity of the user
+    let (before, after) = {
+        let row = database
+            .client
+            .query_one(
+                "
+                INSERT INTO voice_activity
+                VALUES ($1::BIGINT, $2::BIGINT, $3::BIGINT)
+                ON CONFLICT (guild, \"user\")
+                DO UPDATE SET minutes = voice_activity.minutes + $3::BIGINT
+                RETURNING minutes
+                ",
+                &[&guild_db_id, &user_db_id, &minutes],
+            )
+            .await?;
+
+        let after: i64 = row.get(0);
+
+        (after - minutes, after)
+    };
+
+    // Update the roles of the user if the voice score has changed
+    if before / rate != after / rate {
+        let mut member = guild_id.member(ctx, user_id).await?;
+        update_roles(ctx, database, &mut member).await?;
+    }
+
+    Ok(())
+}